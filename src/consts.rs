@@ -46,3 +46,83 @@ pub use ffi::LightType;
 pub use ffi::MouseCursor;
 pub use ffi::PI;
 pub use ffi::RAD2DEG;
+
+/// A gesture detected by raylib's gesture system.
+///
+/// Raylib reports gestures as [`GestureType`] bit flags. This maps them to a plain enum so they can be matched on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gesture {
+    None,
+    Tap,
+    DoubleTap,
+    Hold,
+    Drag,
+    SwipeRight,
+    SwipeLeft,
+    SwipeUp,
+    SwipeDown,
+    PinchIn,
+    PinchOut,
+}
+
+impl Gesture {
+    /// Maps raylib gesture flags to a `Gesture`. Unknown flags map to `Gesture::None`.
+    pub fn from_flags(flags: i32) -> Gesture {
+        use GestureType::*;
+        let has = |g: GestureType| flags & (g as i32) != 0;
+        if has(GESTURE_PINCH_OUT) {
+            Gesture::PinchOut
+        } else if has(GESTURE_PINCH_IN) {
+            Gesture::PinchIn
+        } else if has(GESTURE_DRAG) {
+            Gesture::Drag
+        } else if has(GESTURE_SWIPE_RIGHT) {
+            Gesture::SwipeRight
+        } else if has(GESTURE_SWIPE_LEFT) {
+            Gesture::SwipeLeft
+        } else if has(GESTURE_SWIPE_UP) {
+            Gesture::SwipeUp
+        } else if has(GESTURE_SWIPE_DOWN) {
+            Gesture::SwipeDown
+        } else if has(GESTURE_HOLD) {
+            Gesture::Hold
+        } else if has(GESTURE_DOUBLETAP) {
+            Gesture::DoubleTap
+        } else if has(GESTURE_TAP) {
+            Gesture::Tap
+        } else {
+            Gesture::None
+        }
+    }
+
+    /// Returns the raylib flag for this gesture.
+    pub fn flag(self) -> i32 {
+        use GestureType::*;
+        (match self {
+            Gesture::None => GESTURE_NONE,
+            Gesture::Tap => GESTURE_TAP,
+            Gesture::DoubleTap => GESTURE_DOUBLETAP,
+            Gesture::Hold => GESTURE_HOLD,
+            Gesture::Drag => GESTURE_DRAG,
+            Gesture::SwipeRight => GESTURE_SWIPE_RIGHT,
+            Gesture::SwipeLeft => GESTURE_SWIPE_LEFT,
+            Gesture::SwipeUp => GESTURE_SWIPE_UP,
+            Gesture::SwipeDown => GESTURE_SWIPE_DOWN,
+            Gesture::PinchIn => GESTURE_PINCH_IN,
+            Gesture::PinchOut => GESTURE_PINCH_OUT,
+        }) as i32
+    }
+}
+
+impl Default for Gesture {
+    fn default() -> Gesture {
+        Gesture::None
+    }
+}
+
+impl From<i32> for Gesture {
+    fn from(flags: i32) -> Gesture {
+        Gesture::from_flags(flags)
+    }
+}
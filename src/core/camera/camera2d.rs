@@ -0,0 +1,670 @@
+//! Helpers for moving a [`Camera2D`] around
+use nalgebra::{Scalar, Vector2};
+use num_traits::Float;
+
+use crate::core::camera::{Camera2D, Camera3D};
+use crate::core::math::{deg_to_rad, vec2_from_f32, vec2_to_f32, wrap_degrees};
+
+/// A transient effect drawn on top of a [`Camera2D`], such as shake or a zoom punch.
+///
+/// The 2D counterpart of [`CameraModifier`](crate::core::camera::CameraModifier). Effects that change over time keep their own state and are advanced by the caller.
+pub trait Camera2DEffect<T>
+where
+    T: Float,
+{
+    /// Applies the effect to `cam`.
+    fn apply(&self, cam: &mut Camera2D<T>);
+}
+
+/// How a [`Camera2D`] adapts when the window changes size, see [`Camera2D::apply_resize`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FitMode {
+    /// Keep the same world height in view, the visible width follows the window's shape.
+    ///
+    /// `zoom` scales the same on both axes, so a real non-uniform stretch needs a render texture drawn to the window instead.
+    Stretch,
+    /// Keep everything that was visible in view, showing extra world along the axis that grew relatively more.
+    Letterbox,
+    /// Fill the window at the scale of the axis that grew relatively more, cutting off the edges along the other.
+    Crop,
+}
+
+/// Keeps the logical camera used by gameplay apart from the camera effects are rendered with.
+///
+/// Game logic reads and moves `camera`, [`Camera2DState::render_camera`] returns a copy with the effects applied for drawing.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Camera2DState<T>
+where
+    T: Float,
+{
+    pub camera: Camera2D<T>,
+}
+
+impl<T> Camera2DState<T>
+where
+    T: Float + Scalar,
+{
+    /// Wraps a logical camera.
+    pub fn new(camera: Camera2D<T>) -> Self {
+        Camera2DState { camera }
+    }
+
+    /// Returns the logical camera with `effects` applied in order. The logical camera is not changed.
+    pub fn render_camera(&self, effects: &[&dyn Camera2DEffect<T>]) -> Camera2D<T> {
+        let mut cam = self.camera;
+        for effect in effects {
+            effect.apply(&mut cam);
+        }
+        cam
+    }
+}
+
+/// Shifts a [`Camera2D`]'s `target` ahead of a moving subject so more of where it's heading is on screen.
+///
+/// While moving the offset eases toward `max_offset` in the direction of travel at the exponential rate `smoothing`, and back to zero once the subject stops.
+/// `Camera2D` mirrors raylib's struct and has no room for the smoothed offset, so it's kept here between frames. See [`Camera2D::apply_lookahead`].
+#[derive(Debug, Copy, Clone)]
+pub struct Lookahead2D<T>
+where
+    T: Float,
+{
+    pub max_offset: T,
+    pub smoothing: f32,
+    offset: Vector2<T>,
+}
+
+impl<T> Lookahead2D<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a look-ahead with no offset.
+    pub fn new(max_offset: T, smoothing: f32) -> Self {
+        Lookahead2D {
+            max_offset,
+            smoothing,
+            offset: Vector2::new(T::zero(), T::zero()),
+        }
+    }
+
+    /// Returns the offset currently applied.
+    pub fn offset(&self) -> Vector2<T> {
+        self.offset
+    }
+
+    /// Advances the offset by `dt` seconds for a subject moving at `velocity` and adds it to `camera.target`.
+    ///
+    /// Set `target` to the subject's position each frame before calling this.
+    pub fn apply(&mut self, camera: &mut Camera2D<T>, velocity: Vector2<T>, dt: f32) {
+        let velocity = vec2_to_f32(&velocity);
+        let max_offset = self.max_offset.to_f32().unwrap();
+        let desired = velocity
+            .try_normalize(f32::EPSILON)
+            .map_or_else(Vector2::zeros, |dir| dir * max_offset);
+        let offset = vec2_to_f32(&self.offset);
+        let offset = offset + (desired - offset) * (1.0 - (-self.smoothing * dt).exp());
+        self.offset = vec2_from_f32(&offset);
+        camera.target = vec2_from_f32(&(vec2_to_f32(&camera.target) + offset));
+    }
+}
+
+impl<T> Camera2D<T>
+where
+    T: Float + Scalar,
+{
+    /// Shifts `target` ahead of a subject moving at `velocity`, easing the offset over `dt` seconds.
+    ///
+    /// The smoothed offset lives in `state` since the camera itself can't hold it, see [`Lookahead2D`]. Set `target` to the subject's position each frame before calling this.
+    pub fn apply_lookahead(&mut self, state: &mut Lookahead2D<T>, velocity: Vector2<T>, dt: f32) {
+        state.apply(self, velocity, dt);
+    }
+
+    /// Converts the camera to another precision, see [`Camera3D::cast`].
+    pub fn cast<U>(&self) -> Camera2D<U>
+    where
+        U: Float + Scalar,
+    {
+        Camera2D {
+            offset: self.offset.map(|x| U::from(x).unwrap()),
+            target: self.target.map(|x| U::from(x).unwrap()),
+            rotation: self.rotation,
+            zoom: self.zoom,
+        }
+    }
+
+    /// Centers the camera on the box from `min` to `max` and zooms so it fills the screen, keeping `padding` pixels clear on every side.
+    ///
+    /// The whole box is always visible, so the tighter of the two axes decides the zoom. A box with no size on either axis leaves `zoom` unchanged.
+    pub fn fit_bounds(
+        &mut self,
+        min: Vector2<T>,
+        max: Vector2<T>,
+        screen_size: Vector2<f32>,
+        padding: f32,
+    ) {
+        let two = T::one() + T::one();
+        self.target = Vector2::new((min.x + max.x) / two, (min.y + max.y) / two);
+        self.offset = Vector2::new(
+            T::from(screen_size.x / 2.0).unwrap(),
+            T::from(screen_size.y / 2.0).unwrap(),
+        );
+
+        let size = Vector2::new(
+            (max.x - min.x).abs().to_f32().unwrap(),
+            (max.y - min.y).abs().to_f32().unwrap(),
+        );
+        let available = Vector2::new(
+            (screen_size.x - 2.0 * padding).max(1.0),
+            (screen_size.y - 2.0 * padding).max(1.0),
+        );
+        let zoom = match (size.x > f32::EPSILON, size.y > f32::EPSILON) {
+            (true, true) => (available.x / size.x).min(available.y / size.y),
+            (true, false) => available.x / size.x,
+            (false, true) => available.y / size.y,
+            (false, false) => return,
+        };
+        self.zoom = zoom;
+    }
+
+    /// Frames every point in `points`, for keeping all players on screen in co-op games.
+    ///
+    /// Works like [`Camera2D::fit_bounds`] on the points' bounding box, with the zoom clamped to `[min_zoom, max_zoom]` so the players don't shrink to specks when they spread out.
+    /// A single point is centered without changing the zoom, and no points leaves the camera alone.
+    pub fn frame_points(
+        &mut self,
+        points: &[Vector2<T>],
+        screen_size: Vector2<f32>,
+        padding: f32,
+        min_zoom: f32,
+        max_zoom: f32,
+    ) {
+        let (first, rest) = match points.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        if rest.is_empty() {
+            let zoom = self.zoom;
+            self.fit_bounds(*first, *first, screen_size, padding);
+            self.zoom = zoom;
+            return;
+        }
+
+        let (mut min, mut max) = (*first, *first);
+        for p in rest {
+            min = Vector2::new(min.x.min(p.x), min.y.min(p.y));
+            max = Vector2::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        self.fit_bounds(min, max, screen_size, padding);
+        self.zoom = self.zoom.max(min_zoom).min(max_zoom);
+    }
+
+    /// Like [`Camera2D::frame_points`], but `target` stays put while the center of the points is within `deadzone` world units of it on each axis.
+    ///
+    /// Once the center leaves that rectangle the target is dragged along behind it, so small movements don't jiggle the camera. The zoom is updated every time.
+    pub fn frame_points_with_deadzone(
+        &mut self,
+        points: &[Vector2<T>],
+        screen_size: Vector2<f32>,
+        padding: f32,
+        min_zoom: f32,
+        max_zoom: f32,
+        deadzone: Vector2<T>,
+    ) {
+        let previous = self.target;
+        self.frame_points(points, screen_size, padding, min_zoom, max_zoom);
+        let follow = |old: T, center: T, half: T| {
+            let half = half.abs();
+            if center > old + half {
+                center - half
+            } else if center < old - half {
+                center + half
+            } else {
+                old
+            }
+        };
+        self.target = Vector2::new(
+            follow(previous.x, self.target.x, deadzone.x),
+            follow(previous.y, self.target.y, deadzone.y),
+        );
+    }
+
+    /// Sets `zoom` so that `world_height` world units fill a screen `screen_height` pixels tall.
+    pub fn set_visible_height(&mut self, world_height: T, screen_height: f32) {
+        self.zoom = screen_height / world_height.to_f32().unwrap();
+    }
+
+    /// Rounds `zoom` to the nearest whole number, or to the nearest of 1/2, 1/3, 1/4 and so on when zoomed out, so pixel art scales without shimmering.
+    ///
+    /// A zoom that isn't positive is left alone.
+    pub fn snap_zoom_to_integer(&mut self) {
+        if self.zoom >= 1.0 {
+            self.zoom = self.zoom.round();
+        } else if self.zoom > 0.0 {
+            self.zoom = 1.0 / (1.0 / self.zoom).round();
+        }
+    }
+
+    /// Sets a whole number zoom. Positive levels magnify that many times, negative levels shrink, -2 giving a zoom of 1/2, and 0 is the same as 1.
+    pub fn set_integer_zoom(&mut self, level: i32) {
+        self.zoom = if level < 0 {
+            1.0 / -(level as f32)
+        } else {
+            level.max(1) as f32
+        };
+    }
+
+    /// Eases `rotation` toward `target_degrees`, turning the short way across 0/360. Higher `speed` gets there faster, and the result is the same at any frame rate.
+    ///
+    /// While turning `rotation` may leave the `[0, 360)` range, it lands exactly on `target_degrees` once it's close enough.
+    pub fn smooth_rotate(&mut self, target_degrees: f32, speed: f32, dt: f32) {
+        let delta = wrap_degrees(target_degrees - self.rotation);
+        let remaining = delta * (-speed * dt).exp();
+        self.rotation = if remaining.abs() < 1e-3 {
+            target_degrees
+        } else {
+            self.rotation + delta - remaining
+        };
+    }
+
+    /// Returns how many world units tall the view is on a screen `screen_height` pixels tall.
+    pub fn visible_height(&self, screen_height: f32) -> T {
+        T::from(screen_height / self.zoom).unwrap()
+    }
+
+    /// Adjusts `zoom` and `offset` after the window goes from `old_size` to `new_size` pixels, following `mode`.
+    ///
+    /// `offset` keeps its position relative to the window, so a centered camera stays centered.
+    pub fn apply_resize(&mut self, old_size: Vector2<f32>, new_size: Vector2<f32>, mode: FitMode) {
+        if old_size.x <= 0.0 || old_size.y <= 0.0 {
+            return;
+        }
+        let scale = Vector2::new(new_size.x / old_size.x, new_size.y / old_size.y);
+        self.zoom *= match mode {
+            FitMode::Stretch => scale.y,
+            FitMode::Letterbox => scale.x.min(scale.y),
+            FitMode::Crop => scale.x.max(scale.y),
+        };
+        self.offset = Vector2::new(
+            self.offset.x * T::from(scale.x).unwrap(),
+            self.offset.y * T::from(scale.y).unwrap(),
+        );
+    }
+
+    /// Returns the corners `(min, max)` of the smallest world space rectangle covering everything on a screen of `screen_size` pixels, for deciding which chunks to stream in.
+    ///
+    /// Accounts for `zoom`, `offset` and `rotation` the same way raylib does. A rotated view covers a tilted rectangle, so the result is its bounding box and is larger than the view itself.
+    pub fn visible_world_rect(&self, screen_size: Vector2<f32>) -> (Vector2<T>, Vector2<T>) {
+        let (sin, cos) = deg_to_rad(-self.rotation).sin_cos();
+        let offset = Vector2::new(
+            self.offset.x.to_f32().unwrap(),
+            self.offset.y.to_f32().unwrap(),
+        );
+        let target = Vector2::new(
+            self.target.x.to_f32().unwrap(),
+            self.target.y.to_f32().unwrap(),
+        );
+        let zoom = if self.zoom.abs() > f32::EPSILON {
+            self.zoom
+        } else {
+            f32::EPSILON
+        };
+        let to_world = |screen: Vector2<f32>| {
+            let p = (screen - offset) / zoom;
+            Vector2::new(p.x * cos - p.y * sin, p.x * sin + p.y * cos) + target
+        };
+
+        let corners = [
+            Vector2::zeros(),
+            Vector2::new(screen_size.x, 0.0),
+            screen_size,
+            Vector2::new(0.0, screen_size.y),
+        ];
+        let first = to_world(corners[0]);
+        let (mut min, mut max) = (first, first);
+        for &corner in &corners[1..] {
+            let p = to_world(corner);
+            min = Vector2::new(min.x.min(p.x), min.y.min(p.y));
+            max = Vector2::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        (
+            Vector2::new(T::from(min.x).unwrap(), T::from(min.y).unwrap()),
+            Vector2::new(T::from(max.x).unwrap(), T::from(max.y).unwrap()),
+        )
+    }
+
+    /// Creates a north-up minimap camera centered on `camera3d`, looking down on the XZ plane.
+    ///
+    /// World X maps to screen X and world Z to screen Y, `world_scale` pixels per world unit. Draw into a `minimap_size` viewport, such as a render texture of that size.
+    pub fn minimap_from(
+        camera3d: &Camera3D<T>,
+        world_scale: f32,
+        minimap_size: Vector2<f32>,
+    ) -> Self {
+        Camera2D {
+            offset: Vector2::new(
+                T::from(minimap_size.x / 2.0).unwrap(),
+                T::from(minimap_size.y / 2.0).unwrap(),
+            ),
+            target: Vector2::new(camera3d.position.x, camera3d.position.z),
+            rotation: 0.0,
+            zoom: world_scale,
+        }
+    }
+
+    /// Like [`Camera2D::minimap_from`], but rotated so the direction `camera3d` is facing points up.
+    pub fn minimap_from_heading(
+        camera3d: &Camera3D<T>,
+        world_scale: f32,
+        minimap_size: Vector2<f32>,
+    ) -> Self {
+        let mut minimap = Self::minimap_from(camera3d, world_scale, minimap_size);
+        let (yaw, _) = camera3d.yaw_pitch();
+        minimap.rotation = wrap_degrees(yaw.to_f32().unwrap() - 180.0);
+        minimap
+    }
+
+    /// Creates a 2D camera that lines up with `camera3d` looking straight down, for drawing a 2D layer over the 3D world.
+    ///
+    /// World X maps to 2D X and world Z to 2D Y, `world_to_screen_scale` pixels per world unit, and the 2D `target` is the XZ of the 3D `target`.
+    /// The rotation follows the 3D camera's yaw as in [`Camera2D::minimap_from_heading`], so the 3D up direction stays up on screen. `offset` is left at zero, set it to the center of the screen.
+    pub fn top_down_from(camera3d: &Camera3D<T>, world_to_screen_scale: f32) -> Self {
+        let mut camera =
+            Self::minimap_from_heading(camera3d, world_to_screen_scale, Vector2::zeros());
+        camera.target = Vector2::new(camera3d.target.x, camera3d.target.z);
+        camera
+    }
+}
+
+#[cfg(test)]
+mod test_camera2d {
+    use super::*;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn test_lookahead_caps_and_recenters() {
+        let mut camera = Camera2D::<f32>::default();
+        let mut lookahead = Lookahead2D::new(40.0, 5.0);
+        let player = Vector2::new(100.0, 50.0);
+
+        for _ in 0..200 {
+            camera.target = player;
+            camera.apply_lookahead(&mut lookahead, Vector2::new(300.0, 0.0), 1.0 / 60.0);
+            assert!(camera.target.x - player.x <= 40.0 + 1e-4);
+        }
+        assert!((camera.target.x - player.x - 40.0).abs() < 0.1);
+        assert_eq!(camera.target.y, player.y);
+        assert!((camera.target - player - lookahead.offset()).norm() < 1e-4);
+
+        for _ in 0..400 {
+            camera.target = player;
+            lookahead.apply(&mut camera, Vector2::zeros(), 1.0 / 60.0);
+        }
+        assert!((camera.target - player).norm() < 0.01);
+    }
+
+    #[test]
+    fn test_minimap_follows_camera() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(5.0f32, 10.0, -3.0),
+            Vector3::new(5.0, 10.0, -2.0),
+            Vector3::y(),
+            60.0,
+        );
+        let size = Vector2::new(200.0, 200.0);
+        let before = Camera2D::minimap_from(&camera, 4.0, size);
+        assert_eq!(before.target, Vector2::new(5.0, -3.0));
+        assert_eq!(before.offset, Vector2::new(100.0, 100.0));
+        assert_eq!(before.zoom, 4.0);
+
+        camera.position.x += 10.0;
+        camera.target.x += 10.0;
+        let after = Camera2D::minimap_from(&camera, 4.0, size);
+        assert_eq!(after.target.x - before.target.x, 10.0);
+        assert_eq!(after.target.y, before.target.y);
+
+        // Facing +Z, which is down the minimap until rotated
+        let rotated = Camera2D::minimap_from_heading(&camera, 4.0, size);
+        assert!((rotated.rotation.abs() - 180.0).abs() < 1e-3);
+
+        // Facing +X is right on the minimap, raylib turns it up with a negative (counter-clockwise) rotation
+        camera.target = camera.position + Vector3::x();
+        let rotated = Camera2D::minimap_from_heading(&camera, 4.0, size);
+        assert!((rotated.rotation + 90.0).abs() < 1e-3);
+        let (sin, cos) = deg_to_rad(rotated.rotation).sin_cos();
+        let heading_on_screen = Vector2::new(cos, sin);
+        assert!((heading_on_screen - Vector2::new(0.0, -1.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_top_down_matches_target() {
+        let camera = Camera3D::perspective(
+            Vector3::new(3.0f32, 20.0, 7.0),
+            Vector3::new(3.0, 0.0, 7.0),
+            Vector3::z(),
+            60.0,
+        );
+        let top_down = Camera2D::top_down_from(&camera, 8.0);
+        assert_eq!(top_down.target, Vector2::new(3.0, 7.0));
+        assert_eq!(top_down.zoom, 8.0);
+        assert_eq!(top_down.offset, Vector2::zeros());
+        // Facing +Z with +Z at the top of the screen
+        assert!((top_down.rotation.abs() - 180.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_frame_points_clamps_zoom() {
+        let mut camera = Camera2D::<f32> {
+            zoom: 1.0,
+            ..Default::default()
+        };
+        let screen = Vector2::new(800.0, 600.0);
+        let points = [Vector2::new(-5000.0, 0.0), Vector2::new(5000.0, 100.0)];
+        camera.frame_points(&points, screen, 50.0, 0.25, 4.0);
+        assert_eq!(camera.target, Vector2::new(0.0, 50.0));
+        assert_eq!(camera.offset, Vector2::new(400.0, 300.0));
+        assert_eq!(camera.zoom, 0.25);
+
+        let close = [Vector2::new(0.0, 0.0), Vector2::new(140.0, 0.0)];
+        camera.frame_points(&close, screen, 50.0, 0.25, 4.0);
+        assert_eq!(camera.zoom, 4.0);
+
+        let mid = [Vector2::new(0.0, 0.0), Vector2::new(700.0, 0.0)];
+        camera.frame_points(&mid, screen, 50.0, 0.25, 4.0);
+        assert!((camera.zoom - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_frame_single_point_keeps_zoom() {
+        let mut camera = Camera2D::<f32> {
+            zoom: 2.5,
+            ..Default::default()
+        };
+        camera.frame_points(
+            &[Vector2::new(30.0, -20.0)],
+            Vector2::new(800.0, 600.0),
+            50.0,
+            0.25,
+            1.0,
+        );
+        assert_eq!(camera.target, Vector2::new(30.0, -20.0));
+        assert_eq!(camera.zoom, 2.5);
+    }
+
+    #[test]
+    fn test_frame_points_deadzone() {
+        let mut camera = Camera2D::<f32> {
+            zoom: 1.0,
+            ..Default::default()
+        };
+        let screen = Vector2::new(800.0, 600.0);
+        let deadzone = Vector2::new(20.0, 10.0);
+        let frame = |camera: &mut Camera2D<f32>, shift: Vector2<f32>| {
+            let players = [
+                Vector2::new(-100.0, 0.0) + shift,
+                Vector2::new(100.0, 0.0) + shift,
+            ];
+            camera.frame_points_with_deadzone(&players, screen, 50.0, 0.25, 4.0, deadzone);
+        };
+
+        frame(&mut camera, Vector2::zeros());
+        assert_eq!(camera.target, Vector2::zeros());
+        frame(&mut camera, Vector2::new(15.0, -8.0));
+        assert_eq!(camera.target, Vector2::zeros());
+        // Leaving the deadzone drags the target to its edge
+        frame(&mut camera, Vector2::new(50.0, 0.0));
+        assert_eq!(camera.target, Vector2::new(30.0, 0.0));
+        frame(&mut camera, Vector2::new(30.0, 40.0));
+        assert_eq!(camera.target, Vector2::new(30.0, 30.0));
+    }
+
+    struct Nudge(f32);
+    impl Camera2DEffect<f32> for Nudge {
+        fn apply(&self, cam: &mut Camera2D<f32>) {
+            cam.target.x += self.0;
+        }
+    }
+
+    struct Punch(f32);
+    impl Camera2DEffect<f32> for Punch {
+        fn apply(&self, cam: &mut Camera2D<f32>) {
+            cam.zoom *= self.0;
+        }
+    }
+
+    #[test]
+    fn test_render_camera_leaves_logic_alone() {
+        let state = Camera2DState::new(Camera2D {
+            target: Vector2::new(10.0, 20.0),
+            zoom: 2.0,
+            ..Default::default()
+        });
+        let render = state.render_camera(&[&Nudge(3.0), &Punch(1.5)]);
+        assert_eq!(render.target, Vector2::new(13.0, 20.0));
+        assert_eq!(render.zoom, 3.0);
+        assert_eq!(state.camera.target, Vector2::new(10.0, 20.0));
+        assert_eq!(state.camera.zoom, 2.0);
+        assert_eq!(state.render_camera(&[]).target, state.camera.target);
+    }
+
+    #[test]
+    fn test_apply_resize_modes() {
+        let old = Vector2::new(800.0, 600.0);
+        let resized = |new: Vector2<f32>, mode| {
+            let mut camera = Camera2D::<f32> {
+                offset: Vector2::new(400.0, 300.0),
+                zoom: 1.0,
+                ..Default::default()
+            };
+            camera.apply_resize(old, new, mode);
+            (camera.visible_height(new.y), camera.offset)
+        };
+
+        let wide = Vector2::new(1600.0, 600.0);
+        assert_eq!(resized(wide, FitMode::Stretch).0, 600.0);
+        assert_eq!(resized(wide, FitMode::Letterbox).0, 600.0);
+        assert_eq!(resized(wide, FitMode::Crop).0, 300.0);
+
+        let tall = Vector2::new(800.0, 1200.0);
+        assert_eq!(resized(tall, FitMode::Stretch).0, 600.0);
+        assert_eq!(resized(tall, FitMode::Letterbox).0, 1200.0);
+        assert_eq!(resized(tall, FitMode::Crop).0, 600.0);
+
+        assert_eq!(resized(tall, FitMode::Crop).1, Vector2::new(400.0, 600.0));
+    }
+
+    #[test]
+    fn test_visible_world_rect() {
+        let screen = Vector2::new(800.0, 600.0);
+        let mut camera = Camera2D::<f32> {
+            offset: Vector2::new(400.0, 300.0),
+            target: Vector2::new(1000.0, 50.0),
+            zoom: 1.0,
+            rotation: 0.0,
+        };
+        let (min, max) = camera.visible_world_rect(screen);
+        assert!((min - Vector2::new(600.0, -250.0)).norm() < 1e-3);
+        assert!((max - Vector2::new(1400.0, 350.0)).norm() < 1e-3);
+
+        camera.zoom = 2.0;
+        let (min, max) = camera.visible_world_rect(screen);
+        assert!((max - min - Vector2::new(400.0, 300.0)).norm() < 1e-3);
+
+        // A quarter turn swaps the axes, 45 degrees covers more than either
+        camera.zoom = 1.0;
+        camera.rotation = 90.0;
+        let (min, max) = camera.visible_world_rect(screen);
+        assert!((max - min - Vector2::new(600.0, 800.0)).norm() < 1e-2);
+        camera.rotation = 45.0;
+        let (min, max) = camera.visible_world_rect(screen);
+        assert!(max.x - min.x > 800.0 && max.y - min.y > 800.0);
+        assert!(((min + max) / 2.0 - camera.target).norm() < 1e-2);
+    }
+
+    #[test]
+    fn test_snap_zoom_to_integer() {
+        let snapped = |zoom: f32| {
+            let mut camera = Camera2D::<f32> {
+                zoom,
+                ..Default::default()
+            };
+            camera.snap_zoom_to_integer();
+            camera.zoom
+        };
+        assert_eq!(snapped(2.4), 2.0);
+        assert_eq!(snapped(2.6), 3.0);
+        assert_eq!(snapped(1.2), 1.0);
+        assert_eq!(snapped(0.45), 0.5);
+        assert_eq!(snapped(0.3), 1.0 / 3.0);
+        assert_eq!(snapped(0.26), 0.25);
+
+        let mut camera = Camera2D::<f32>::default();
+        camera.set_integer_zoom(4);
+        assert_eq!(camera.zoom, 4.0);
+        camera.set_integer_zoom(-2);
+        assert_eq!(camera.zoom, 0.5);
+        camera.set_integer_zoom(0);
+        assert_eq!(camera.zoom, 1.0);
+    }
+
+    #[test]
+    fn test_smooth_rotate_short_way() {
+        let mut camera = Camera2D::<f32> {
+            rotation: 350.0,
+            ..Default::default()
+        };
+        let mut previous = camera.rotation;
+        for _ in 0..600 {
+            camera.smooth_rotate(10.0, 6.0, 1.0 / 60.0);
+            if camera.rotation == 10.0 {
+                break;
+            }
+            // Counts up through 360 rather than down through 180, and never passes the target
+            assert!(camera.rotation > previous && camera.rotation < 370.0);
+            previous = camera.rotation;
+        }
+        assert!(previous > 360.0);
+        assert_eq!(camera.rotation, 10.0);
+
+        // The same time in bigger steps ends in the same place
+        let turn = |steps: usize| {
+            let mut camera = Camera2D::<f32> {
+                rotation: 350.0,
+                ..Default::default()
+            };
+            for _ in 0..steps {
+                camera.smooth_rotate(10.0, 2.0, 0.5 / steps as f32);
+            }
+            camera.rotation
+        };
+        assert!((turn(5) - turn(50)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_visible_height() {
+        let mut camera = Camera2D::<f32>::default();
+        camera.set_visible_height(20.0, 720.0);
+        assert_eq!(camera.zoom, 36.0);
+        assert_eq!(camera.visible_height(720.0), 20.0);
+        assert_eq!(camera.visible_height(1080.0), 30.0);
+    }
+}
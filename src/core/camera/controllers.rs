@@ -0,0 +1,1320 @@
+//! Camera controllers driven by a [`CameraInput`]
+use nalgebra::{Scalar, UnitQuaternion, Vector2, Vector3};
+use num_traits::Float;
+
+use crate::consts::{Gesture, KeyboardKey};
+use crate::core::camera::{lerp_cameras, Camera2D, Camera3D, CameraInput, CameraPath};
+use crate::core::collision::{get_collision_ray_ground, BoundingBox};
+use crate::core::math::{
+    cartesian_to_spherical, clamp_pitch, deg_to_rad, rad_to_deg, spherical_to_cartesian,
+    vec3_from_f32, vec3_to_f32, wrap_degrees, MAX_CAMERA_PITCH,
+};
+use crate::core::RaylibHandle;
+use crate::ease::{linear_none, EaseFn};
+
+/// Holds on to edge-triggered key presses until they are consumed.
+///
+/// `key_pressed` is only true for the single frame a key went down, so a controller that skips updates (while loading, or when running at a lower rate than the render loop) can miss a toggle entirely.
+/// Call [`InputLatch::poll`] every frame, and [`InputLatch::consume`] whenever the controller actually updates.
+#[derive(Debug, Clone, Default)]
+pub struct InputLatch {
+    watched: Vec<KeyboardKey>,
+    latched: Vec<KeyboardKey>,
+}
+
+impl InputLatch {
+    /// Creates a latch watching the given keys.
+    pub fn new(keys: &[KeyboardKey]) -> Self {
+        InputLatch {
+            watched: keys.to_vec(),
+            latched: Vec::new(),
+        }
+    }
+
+    /// Starts watching another key.
+    pub fn watch(&mut self, key: KeyboardKey) {
+        if !self.watched.contains(&key) {
+            self.watched.push(key);
+        }
+    }
+
+    /// Records any watched key pressed this frame. Call this once per frame.
+    pub fn poll(&mut self, input: &dyn CameraInput) {
+        for key in &self.watched {
+            if input.key_pressed(*key) && !self.latched.contains(key) {
+                self.latched.push(*key);
+            }
+        }
+    }
+
+    /// Returns true if `key` was pressed since it was last consumed, and clears it.
+    pub fn consume(&mut self, key: KeyboardKey) -> bool {
+        match self.latched.iter().position(|k| *k == key) {
+            Some(i) => {
+                self.latched.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true if `key` is waiting to be consumed, without clearing it.
+    pub fn is_latched(&self, key: KeyboardKey) -> bool {
+        self.latched.contains(&key)
+    }
+
+    /// Drops every pending press.
+    pub fn clear(&mut self) {
+        self.latched.clear();
+    }
+}
+
+/// Pans and zooms a [`Camera2D`] from touch gestures.
+///
+/// Drags move the camera so the world follows the finger, and pinches scale `zoom` by how far the fingers spread or closed since the last update.
+/// Raylib reports gesture vectors in normalized screen units, so `pan_speed` is normally the screen size in pixels.
+#[derive(Debug, Clone)]
+pub struct TouchPanController {
+    pub pan_speed: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    last_drag: Option<Vector2<f32>>,
+    last_pinch: Option<f32>,
+}
+
+impl TouchPanController {
+    /// Creates a controller allowing zoom between 0.1 and 10.
+    pub fn new(pan_speed: f32) -> Self {
+        TouchPanController {
+            pan_speed,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            last_drag: None,
+            last_pinch: None,
+        }
+    }
+
+    /// Applies this frame's gesture to the camera.
+    pub fn update<T>(&mut self, camera: &mut Camera2D<T>, input: &dyn CameraInput)
+    where
+        T: Float + Scalar,
+    {
+        match input.gesture() {
+            Gesture::Drag => {
+                // The drag vector is measured from where the drag started, only move by what changed
+                let drag = input.gesture_drag_vector();
+                let delta = drag - self.last_drag.unwrap_or_else(Vector2::zeros);
+                self.last_drag = Some(drag);
+                self.last_pinch = None;
+
+                let scale = self.pan_speed / camera.zoom;
+                camera.target.x = camera.target.x - T::from(delta.x * scale).unwrap();
+                camera.target.y = camera.target.y - T::from(delta.y * scale).unwrap();
+            }
+            Gesture::PinchIn | Gesture::PinchOut => {
+                let spread = input.gesture_pinch_vector().norm();
+                if let Some(last) = self.last_pinch {
+                    if last > f32::EPSILON {
+                        camera.zoom = (camera.zoom * spread / last)
+                            .max(self.min_zoom)
+                            .min(self.max_zoom);
+                    }
+                }
+                self.last_pinch = Some(spread);
+                self.last_drag = None;
+            }
+            _ => {
+                self.last_drag = None;
+                self.last_pinch = None;
+            }
+        }
+    }
+}
+
+/// Scrolls the map when the mouse nears the edge of the screen, like in RTS games.
+///
+/// Inside `edge_margin` pixels of a border the camera moves toward that border, going faster the closer the cursor gets, up to `speed` at the very edge.
+/// `speed` is in screen pixels per second for a [`Camera2D`], and world units per second on the XZ plane for a [`Camera3D`].
+#[derive(Debug, Clone)]
+pub struct EdgeScrollController {
+    pub edge_margin: f32,
+    pub speed: f32,
+}
+
+impl EdgeScrollController {
+    /// Creates a controller with the given margin and speed.
+    pub fn new(edge_margin: f32, speed: f32) -> Self {
+        EdgeScrollController { edge_margin, speed }
+    }
+
+    /// Returns which way to scroll for the mouse at `mouse`, with x to the right and y down the screen.
+    ///
+    /// Each component goes from 0 at the inner edge of the margin to 1 at the border, so a corner scrolls diagonally.
+    pub fn scroll_direction(&self, mouse: Vector2<f32>, screen_size: Vector2<f32>) -> Vector2<f32> {
+        let margin = self.edge_margin.max(f32::EPSILON);
+        let axis = |pos: f32, size: f32| {
+            let amount = if pos < margin {
+                -(margin - pos) / margin
+            } else if pos > size - margin {
+                (pos - (size - margin)) / margin
+            } else {
+                0.0
+            };
+            amount.max(-1.0).min(1.0)
+        };
+        Vector2::new(axis(mouse.x, screen_size.x), axis(mouse.y, screen_size.y))
+    }
+
+    /// Pans a 2D camera, keeping `target` between the corners of `bounds` if given.
+    pub fn update<T>(
+        &self,
+        camera: &mut Camera2D<T>,
+        input: &dyn CameraInput,
+        screen_size: Vector2<f32>,
+        bounds: Option<(Vector2<T>, Vector2<T>)>,
+        dt: f32,
+    ) where
+        T: Float + Scalar,
+    {
+        let step = self.scroll_direction(input.mouse_position(), screen_size) * self.speed * dt
+            / camera.zoom;
+        camera.target.x = camera.target.x + T::from(step.x).unwrap();
+        camera.target.y = camera.target.y + T::from(step.y).unwrap();
+        if let Some((min, max)) = bounds {
+            camera.target.x = camera.target.x.max(min.x).min(max.x);
+            camera.target.y = camera.target.y.max(min.y).min(max.y);
+        }
+    }
+
+    /// Slides a 3D camera across the XZ plane, with the top of the screen moving along the camera's heading.
+    ///
+    /// The camera keeps its height and view direction, and is held inside `bounds` with [`Camera3D::clamp_position_to_box`].
+    pub fn update_3d<T>(
+        &self,
+        camera: &mut Camera3D<T>,
+        input: &dyn CameraInput,
+        screen_size: Vector2<f32>,
+        bounds: Option<&BoundingBox<T>>,
+        dt: f32,
+    ) where
+        T: Float + Scalar,
+    {
+        let (yaw, _) = camera.yaw_pitch();
+        let yaw = deg_to_rad(yaw.to_f32().unwrap());
+        let forward = Vector3::new(yaw.sin(), 0.0, yaw.cos());
+        let right = Vector3::new(-yaw.cos(), 0.0, yaw.sin());
+
+        let dir = self.scroll_direction(input.mouse_position(), screen_size);
+        let step = (right * dir.x - forward * dir.y) * self.speed * dt;
+        camera.position = vec3_from_f32(&(vec3_to_f32(&camera.position) + step));
+        camera.target = vec3_from_f32(&(vec3_to_f32(&camera.target) + step));
+        if let Some(bounds) = bounds {
+            camera.clamp_position_to_box(bounds);
+        }
+    }
+}
+
+/// Orbits a [`Camera3D`] around a target point.
+///
+/// `azimuth` is the angle in degrees around the Y axis (0 places the camera on the +Z side of the target), and `elevation` is the angle in degrees above the XZ plane.
+/// The arrow keys rotate the camera at `rotate_speed` degrees per second.
+#[derive(Debug, Clone)]
+pub struct OrbitController<T>
+where
+    T: Float,
+{
+    pub target: Vector3<T>,
+    pub distance: T,
+    pub azimuth: f32,
+    pub elevation: f32,
+    pub rotate_speed: f32,
+}
+
+impl<T> OrbitController<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a controller looking at `target` from `distance` away, level with it.
+    pub fn new(target: Vector3<T>, distance: T) -> Self {
+        OrbitController {
+            target,
+            distance,
+            azimuth: 0.0,
+            elevation: 0.0,
+            rotate_speed: 90.0,
+        }
+    }
+
+    /// Returns where the camera sits for the current angles.
+    pub fn position(&self) -> Vector3<T> {
+        let offset = spherical_to_cartesian(
+            deg_to_rad(self.azimuth),
+            deg_to_rad(self.elevation),
+            self.distance.to_f32().unwrap(),
+        );
+        vec3_from_f32(&(vec3_to_f32(&self.target) + offset))
+    }
+
+    /// Moves the camera to the current orbit position, looking at the target.
+    pub fn apply(&self, camera: &mut Camera3D<T>) {
+        camera.position = self.position();
+        camera.target = self.target;
+        camera.up = Vector3::new(T::zero(), T::one(), T::zero());
+    }
+
+    /// Rotates with the arrow keys, then applies the result to the camera.
+    pub fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        use crate::consts::KeyboardKey::*;
+        let step = self.rotate_speed * dt;
+        if input.key_down(KEY_LEFT) {
+            self.azimuth -= step;
+        }
+        if input.key_down(KEY_RIGHT) {
+            self.azimuth += step;
+        }
+        if input.key_down(KEY_UP) {
+            self.elevation += step;
+        }
+        if input.key_down(KEY_DOWN) {
+            self.elevation -= step;
+        }
+        self.azimuth = wrap_degrees(self.azimuth);
+        self.elevation = clamp_pitch(self.elevation, -MAX_CAMERA_PITCH, MAX_CAMERA_PITCH);
+        self.apply(camera);
+    }
+
+    /// Eases `azimuth` toward the angle directly behind `facing`, leaving `elevation` alone.
+    ///
+    /// Always turns the short way around, so swinging from 170° to -170° passes through 180°.
+    /// `speed` is the exponential approach rate, higher values settle faster.
+    pub fn recenter_behind(&mut self, facing: Vector3<T>, speed: f32, dt: f32) {
+        let facing = vec3_to_f32(&facing);
+        if facing.x.abs() < f32::EPSILON && facing.z.abs() < f32::EPSILON {
+            // Facing straight up or down, there is no "behind"
+            return;
+        }
+        let goal = rad_to_deg((-facing.x).atan2(-facing.z));
+        let delta = wrap_degrees(goal - self.azimuth);
+        let t = 1.0 - (-speed * dt).exp();
+        self.azimuth = wrap_degrees(self.azimuth + delta * t);
+    }
+}
+
+/// A six degrees of freedom flying camera, for editors and flight sims.
+///
+/// Orientation is kept as a quaternion, so the camera can loop and roll freely without gimbal lock.
+/// W/S fly forward and back, A/D strafe, Space/Left Control move along the camera's up axis, the arrow keys pitch and yaw, and Q/E roll.
+/// `rotate_speed` and `roll_speed` are in degrees per second.
+#[derive(Debug, Clone)]
+pub struct FreeFlyController<T>
+where
+    T: Float,
+{
+    pub position: Vector3<T>,
+    pub move_speed: f32,
+    pub rotate_speed: f32,
+    pub roll_speed: f32,
+    /// Degrees turned per pixel of mouse movement.
+    pub mouse_sensitivity: f32,
+    orientation: UnitQuaternion<f32>,
+}
+
+impl<T> FreeFlyController<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a controller starting from the camera's current pose.
+    pub fn from_camera(camera: &Camera3D<T>) -> Self {
+        let dir = vec3_to_f32(&camera.target) - vec3_to_f32(&camera.position);
+        let orientation = if dir.norm() > f32::EPSILON {
+            // face_towards points +Z along the direction given, the camera looks down -Z
+            UnitQuaternion::face_towards(&-dir, &vec3_to_f32(&camera.up))
+        } else {
+            UnitQuaternion::identity()
+        };
+        FreeFlyController {
+            position: camera.position,
+            move_speed: 10.0,
+            rotate_speed: 90.0,
+            roll_speed: 90.0,
+            mouse_sensitivity: 0.1,
+            orientation,
+        }
+    }
+
+    /// Returns the current orientation.
+    pub fn orientation(&self) -> UnitQuaternion<f32> {
+        self.orientation
+    }
+
+    /// Moves the camera to the controller's pose.
+    pub fn apply(&self, camera: &mut Camera3D<T>) {
+        let position = vec3_to_f32(&self.position);
+        let forward = self.orientation * Vector3::new(0.0, 0.0, -1.0);
+        camera.position = self.position;
+        camera.target = vec3_from_f32(&(position + forward));
+        camera.up = vec3_from_f32(&(self.orientation * Vector3::y()));
+        camera.reorthonormalize();
+    }
+
+    /// Reads flight controls, then applies the result to the camera.
+    pub fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        use crate::consts::KeyboardKey::*;
+        let axis = |positive: KeyboardKey, negative: KeyboardKey| {
+            (input.key_down(positive) as i32 - input.key_down(negative) as i32) as f32
+        };
+
+        let turn = deg_to_rad(self.rotate_speed) * dt;
+        let mouse = input.mouse_delta() * deg_to_rad(self.mouse_sensitivity);
+        let yaw = axis(KEY_LEFT, KEY_RIGHT) * turn - mouse.x;
+        let pitch = axis(KEY_UP, KEY_DOWN) * turn - mouse.y;
+        let roll = axis(KEY_Q, KEY_E) * deg_to_rad(self.roll_speed) * dt;
+        // Rotate about the camera's own axes rather than the world's
+        self.orientation = self.orientation
+            * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw)
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), pitch)
+            * UnitQuaternion::from_axis_angle(&Vector3::z_axis(), roll);
+        self.orientation.renormalize();
+
+        let local = Vector3::new(
+            axis(KEY_D, KEY_A),
+            axis(KEY_SPACE, KEY_LEFT_CONTROL),
+            axis(KEY_S, KEY_W),
+        );
+        let step = self.orientation * local * self.move_speed * dt;
+        self.position = vec3_from_f32(&(vec3_to_f32(&self.position) + step));
+
+        self.apply(camera);
+    }
+}
+
+/// What a [`FirstPersonController`]'s movement input amounted to on its last update, for picking character animations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MovementState {
+    Idle,
+    Walking,
+    /// Moving forward with the sprint key held.
+    Sprinting,
+    /// Moving only sideways.
+    Strafing,
+}
+
+/// Mouse-look and WASD walking, with the camera kept upright.
+///
+/// Unlike [`FreeFlyController`], movement stays on the XZ plane and pitch is limited to `max_pitch` so the view can't flip over.
+/// Holding left shift while moving forward sprints at `sprint_multiplier` times the speed.
+/// Mouse-look adds to the `yaw` and `pitch` accumulators and the view is rebuilt from them with a world-up `up` every frame, so long sessions can't build up roll from rounding errors.
+#[derive(Debug, Clone)]
+pub struct FirstPersonController<T>
+where
+    T: Float,
+{
+    pub position: Vector3<T>,
+    /// Heading in degrees, see [`Camera3D::yaw_pitch`].
+    pub yaw: f32,
+    /// Elevation in degrees, see [`Camera3D::yaw_pitch`].
+    pub pitch: f32,
+    pub max_pitch: f32,
+    pub move_speed: f32,
+    pub sprint_multiplier: f32,
+    /// Degrees turned per pixel of mouse movement.
+    pub mouse_sensitivity: f32,
+    /// Move the cursor back to the middle of the screen in [`FirstPersonController::recenter_cursor`].
+    ///
+    /// A fallback for platforms where `disable_cursor` doesn't lock the cursor properly and it ends up stuck at the edge of the window.
+    pub manual_recenter: bool,
+    input_vector: Vector2<f32>,
+    sprinting: bool,
+}
+
+impl<T> FirstPersonController<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a controller starting from the camera's current pose.
+    pub fn from_camera(camera: &Camera3D<T>) -> Self {
+        let (yaw, pitch) = camera.yaw_pitch();
+        FirstPersonController {
+            position: camera.position,
+            yaw: yaw.to_f32().unwrap(),
+            pitch: pitch.to_f32().unwrap(),
+            max_pitch: 89.0,
+            move_speed: 5.0,
+            sprint_multiplier: 1.6,
+            mouse_sensitivity: 0.1,
+            manual_recenter: false,
+            input_vector: Vector2::zeros(),
+            sprinting: false,
+        }
+    }
+
+    /// Moves the camera to the controller's pose, with `target` one unit in front of `position`.
+    ///
+    /// The target is rebuilt from the angles rather than turned with [`Camera3D::set_yaw_pitch`], which would keep the shrinking distance to the old target while walking toward it.
+    pub fn apply(&self, camera: &mut Camera3D<T>) {
+        let dir = spherical_to_cartesian(deg_to_rad(self.yaw), deg_to_rad(self.pitch), 1.0);
+        camera.position = self.position;
+        camera.target = vec3_from_f32(&(vec3_to_f32(&self.position) + dir));
+        camera.up = Vector3::new(T::zero(), T::one(), T::zero());
+    }
+
+    /// Returns the movement keys held on the last update, as x to the right and y forward, each -1, 0 or 1.
+    pub fn input_vector(&self) -> Vector2<f32> {
+        self.input_vector
+    }
+
+    /// Classifies the movement input from the last update.
+    pub fn movement_state(&self) -> MovementState {
+        let v = self.input_vector;
+        if v == Vector2::zeros() {
+            MovementState::Idle
+        } else if self.sprinting {
+            MovementState::Sprinting
+        } else if v.y == 0.0 {
+            MovementState::Strafing
+        } else {
+            MovementState::Walking
+        }
+    }
+
+    /// Moves the cursor to the middle of the screen if `manual_recenter` is set. Call this once per frame after [`FirstPersonController::update`].
+    pub fn recenter_cursor(&self, rl: &mut RaylibHandle) {
+        if self.manual_recenter {
+            let (width, height) = (rl.get_screen_width(), rl.get_screen_height());
+            rl.set_mouse_position(width / 2, height / 2);
+        }
+    }
+
+    /// Reads mouse-look and walking controls, then applies the result to the camera.
+    pub fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        use crate::consts::KeyboardKey::*;
+        let axis = |positive: KeyboardKey, negative: KeyboardKey| {
+            (input.key_down(positive) as i32 - input.key_down(negative) as i32) as f32
+        };
+
+        // Moving the mouse right or down turns right or down
+        let mouse = input.mouse_delta() * self.mouse_sensitivity;
+        self.yaw = wrap_degrees(self.yaw - mouse.x);
+        self.pitch = clamp_pitch(self.pitch - mouse.y, -self.max_pitch, self.max_pitch);
+
+        self.input_vector = Vector2::new(axis(KEY_D, KEY_A), axis(KEY_W, KEY_S));
+        self.sprinting = self.input_vector.y > 0.0 && input.key_down(KEY_LEFT_SHIFT);
+        let speed = if self.sprinting {
+            self.move_speed * self.sprint_multiplier
+        } else {
+            self.move_speed
+        };
+
+        let yaw = deg_to_rad(self.yaw);
+        let forward = Vector3::new(yaw.sin(), 0.0, yaw.cos());
+        let right = Vector3::new(-yaw.cos(), 0.0, yaw.sin());
+        let step = (forward * self.input_vector.y + right * self.input_vector.x)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::zeros)
+            * speed
+            * dt;
+        self.position = vec3_from_f32(&(vec3_to_f32(&self.position) + step));
+
+        self.apply(camera);
+    }
+}
+
+/// Moves the camera along a [`CameraPath`] on its own, while the player aims freely around the direction of travel.
+///
+/// `t` advances by `speed` per second, so a speed of 0.1 rides the whole path in ten seconds. The mouse turns the view away from the path's direction by at most `max_yaw` and `max_pitch` degrees.
+#[derive(Debug, Clone)]
+pub struct RailController<T>
+where
+    T: Float + Scalar,
+{
+    pub path: CameraPath<T>,
+    pub t: f32,
+    pub speed: f32,
+    /// Start over from the beginning of the path after reaching the end.
+    pub looping: bool,
+    pub max_yaw: f32,
+    pub max_pitch: f32,
+    /// Degrees turned per pixel of mouse movement.
+    pub mouse_sensitivity: f32,
+    yaw: f32,
+    pitch: f32,
+}
+
+impl<T> RailController<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a controller at the start of `path`, looking along it.
+    pub fn new(path: CameraPath<T>, speed: f32) -> Self {
+        RailController {
+            path,
+            t: 0.0,
+            speed,
+            looping: false,
+            max_yaw: 60.0,
+            max_pitch: 45.0,
+            mouse_sensitivity: 0.1,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+
+    /// Returns how far the view is turned away from the direction of travel, as yaw and pitch in degrees.
+    pub fn aim(&self) -> (f32, f32) {
+        (self.yaw, self.pitch)
+    }
+
+    /// Returns true once a non-looping ride has reached the end of the path.
+    pub fn finished(&self) -> bool {
+        !self.looping && self.t >= 1.0
+    }
+
+    /// Moves the camera to the controller's pose.
+    pub fn apply(&self, camera: &mut Camera3D<T>) {
+        let (heading, elevation, _) =
+            cartesian_to_spherical(vec3_to_f32(&self.path.tangent(self.t)));
+        camera.position = self.path.sample(self.t);
+        camera.up = Vector3::new(T::zero(), T::one(), T::zero());
+        camera.set_yaw_pitch(
+            T::from(rad_to_deg(heading) + self.yaw).unwrap(),
+            T::from(clamp_pitch(
+                rad_to_deg(elevation) + self.pitch,
+                -MAX_CAMERA_PITCH,
+                MAX_CAMERA_PITCH,
+            ))
+            .unwrap(),
+        );
+    }
+
+    /// Advances along the path and reads mouse aim, then applies the result to the camera.
+    pub fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        self.t += self.speed * dt;
+        self.t = if self.looping {
+            self.t.rem_euclid(1.0)
+        } else {
+            self.t.max(0.0).min(1.0)
+        };
+
+        let mouse = input.mouse_delta() * self.mouse_sensitivity;
+        self.yaw = (self.yaw - mouse.x).max(-self.max_yaw).min(self.max_yaw);
+        self.pitch = clamp_pitch(self.pitch - mouse.y, -self.max_pitch, self.max_pitch);
+
+        self.apply(camera);
+    }
+}
+
+/// Follows a player from above at a fixed height and pitch, leaning toward the aim cursor, for top-down and twin-stick games.
+///
+/// The camera looks along `heading` degrees (0 faces +Z), `pitch` degrees below the horizon, from `height` world units above `player`.
+/// The spot the cursor points at on the player's ground plane pulls the view `aim_fraction` of the way toward it, by at most `max_aim_offset`, easing at the exponential rate `smoothing`.
+#[derive(Debug, Clone)]
+pub struct TopDownController<T>
+where
+    T: Float,
+{
+    pub player: Vector3<T>,
+    pub height: T,
+    pub pitch: f32,
+    pub heading: f32,
+    pub aim_fraction: f32,
+    pub max_aim_offset: T,
+    pub smoothing: f32,
+    aim_offset: Vector3<f32>,
+}
+
+impl<T> TopDownController<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a controller `height` above `player` looking down at 60 degrees, leaning a third of the way toward the cursor by at most 4 units.
+    pub fn new(player: Vector3<T>, height: T) -> Self {
+        TopDownController {
+            player,
+            height,
+            pitch: 60.0,
+            heading: 0.0,
+            aim_fraction: 1.0 / 3.0,
+            max_aim_offset: T::from(4.0).unwrap(),
+            smoothing: 8.0,
+            aim_offset: Vector3::zeros(),
+        }
+    }
+
+    /// Returns how far the view is pulled toward the cursor, on the XZ plane.
+    pub fn aim_offset(&self) -> Vector3<T> {
+        vec3_from_f32(&self.aim_offset)
+    }
+
+    /// Moves the camera to look at the player shifted by `offset`.
+    fn place(&self, camera: &mut Camera3D<T>, offset: Vector3<f32>) {
+        let pitch = deg_to_rad(clamp_pitch(self.pitch, 10.0, MAX_CAMERA_PITCH));
+        let heading = deg_to_rad(self.heading);
+        let height = self.height.to_f32().unwrap();
+        let focus = vec3_to_f32(&self.player) + offset;
+        let back = Vector3::new(heading.sin(), 0.0, heading.cos()) * (height / pitch.tan());
+        camera.position = vec3_from_f32(&(focus - back + Vector3::y() * height));
+        camera.target = vec3_from_f32(&focus);
+        camera.up = Vector3::new(T::zero(), T::one(), T::zero());
+    }
+
+    /// Moves the camera to the controller's pose.
+    pub fn apply(&self, camera: &mut Camera3D<T>) {
+        self.place(camera, self.aim_offset);
+    }
+
+    /// Eases the aim offset toward the cursor, then applies the result to the camera.
+    ///
+    /// The cursor is traced from the view centered on the player, so holding the mouse still settles the camera instead of chasing the cursor.
+    pub fn update(
+        &mut self,
+        camera: &mut Camera3D<T>,
+        input: &dyn CameraInput,
+        screen_size: Vector2<f32>,
+        dt: f32,
+    ) {
+        let mut centered = *camera;
+        self.place(&mut centered, Vector3::zeros());
+        let player = vec3_to_f32(&self.player);
+        let ray = centered.screen_to_ray(input.mouse_position(), screen_size);
+        let desired = get_collision_ray_ground(&ray, player.y)
+            .map(|cursor| {
+                let max = self.max_aim_offset.to_f32().unwrap();
+                let offset = (cursor - player) * self.aim_fraction;
+                if offset.norm() > max {
+                    offset.normalize() * max
+                } else {
+                    offset
+                }
+            })
+            .unwrap_or_else(Vector3::zeros);
+        self.aim_offset += (desired - self.aim_offset) * (1.0 - (-self.smoothing * dt).exp());
+        self.apply(camera);
+    }
+}
+
+/// A controller that moves a [`Camera3D`] from input every frame, so controllers can be swapped at runtime or blended with [`ControllerBlend`].
+pub trait CameraController<T>
+where
+    T: Float,
+{
+    /// Reads `input`, then applies the result to `camera`.
+    fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32);
+}
+
+impl<T> CameraController<T> for OrbitController<T>
+where
+    T: Float + Scalar,
+{
+    fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        OrbitController::update(self, camera, input, dt);
+    }
+}
+
+impl<T> CameraController<T> for FreeFlyController<T>
+where
+    T: Float + Scalar,
+{
+    fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        FreeFlyController::update(self, camera, input, dt);
+    }
+}
+
+impl<T> CameraController<T> for FirstPersonController<T>
+where
+    T: Float + Scalar,
+{
+    fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        FirstPersonController::update(self, camera, input, dt);
+    }
+}
+
+impl<T> CameraController<T> for RailController<T>
+where
+    T: Float + Scalar,
+{
+    fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        RailController::update(self, camera, input, dt);
+    }
+}
+
+/// Switches between two controllers gradually, such as from first person to orbiting.
+///
+/// Both controllers keep running on their own copy of the camera for `duration` seconds while the output blends from the outgoing one to the incoming one with [`lerp_cameras`], following `curve`.
+/// After that the outgoing controller is dropped and the incoming one drives the camera directly.
+pub struct ControllerBlend<T>
+where
+    T: Float,
+{
+    pub duration: f32,
+    /// Any of the [`ease`](crate::ease) functions, [`linear_none`] by default.
+    pub curve: EaseFn,
+    outgoing: Option<(Box<dyn CameraController<T>>, Camera3D<T>)>,
+    incoming: Box<dyn CameraController<T>>,
+    incoming_camera: Camera3D<T>,
+    elapsed: f32,
+}
+
+impl<T> ControllerBlend<T>
+where
+    T: Float + Scalar,
+{
+    /// Starts blending from `outgoing` to `incoming`, both starting from `camera`.
+    pub fn new(
+        camera: &Camera3D<T>,
+        outgoing: impl CameraController<T> + 'static,
+        incoming: impl CameraController<T> + 'static,
+        duration: f32,
+    ) -> Self {
+        ControllerBlend {
+            duration,
+            curve: linear_none,
+            outgoing: Some((Box::new(outgoing), *camera)),
+            incoming: Box::new(incoming),
+            incoming_camera: *camera,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Returns how far through the blend it is, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns true once the incoming controller has taken over completely.
+    pub fn finished(&self) -> bool {
+        self.outgoing.is_none()
+    }
+
+    /// Gives up the incoming controller, for when the blend is no longer needed.
+    pub fn into_incoming(self) -> Box<dyn CameraController<T>> {
+        self.incoming
+    }
+}
+
+impl<T> CameraController<T> for ControllerBlend<T>
+where
+    T: Float + Scalar,
+{
+    fn update(&mut self, camera: &mut Camera3D<T>, input: &dyn CameraInput, dt: f32) {
+        self.incoming.update(&mut self.incoming_camera, input, dt);
+        if self.outgoing.is_none() {
+            *camera = self.incoming_camera;
+            return;
+        }
+
+        self.elapsed += dt;
+        let progress = self.progress();
+        let t = (self.curve)(progress, 0.0, 1.0, 1.0);
+        if let Some((outgoing, outgoing_camera)) = self.outgoing.as_mut() {
+            outgoing.update(outgoing_camera, input, dt);
+            *camera = lerp_cameras(outgoing_camera, &self.incoming_camera, t);
+        }
+        if progress >= 1.0 {
+            self.outgoing = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_controllers {
+    use super::*;
+    use crate::consts::KeyboardKey::*;
+    use crate::core::camera::input::FakeInput;
+
+    #[test]
+    fn test_latch_survives_skipped_update() {
+        let mut latch = InputLatch::new(&[KEY_C]);
+        let mut input = FakeInput::default();
+
+        // Pressed on a frame where the controller didn't update...
+        input.pressed.push(KEY_C);
+        latch.poll(&input);
+        // ...and released again before it did.
+        input.pressed.clear();
+        latch.poll(&input);
+
+        assert!(latch.consume(KEY_C));
+        assert!(
+            !latch.consume(KEY_C),
+            "a press should only be consumed once"
+        );
+    }
+
+    #[test]
+    fn test_latch_ignores_unwatched_keys() {
+        let mut latch = InputLatch::new(&[KEY_C]);
+        let mut input = FakeInput::default();
+        input.pressed.push(KEY_V);
+        latch.poll(&input);
+        assert!(!latch.consume(KEY_V));
+    }
+
+    fn camera2d() -> Camera2D<f32> {
+        Camera2D {
+            zoom: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pinch_adjusts_zoom() {
+        let mut controller = TouchPanController::new(800.0);
+        let mut camera = camera2d();
+        let mut input = FakeInput::default();
+
+        input.gesture = Gesture::PinchOut;
+        input.pinch = Vector2::new(0.2, 0.0);
+        controller.update(&mut camera, &input);
+        assert_eq!(
+            camera.zoom, 1.0,
+            "the first pinch frame has nothing to compare to"
+        );
+
+        input.pinch = Vector2::new(0.3, 0.0);
+        controller.update(&mut camera, &input);
+        assert!((camera.zoom - 1.5).abs() < 1e-5);
+
+        input.gesture = Gesture::PinchIn;
+        input.pinch = Vector2::new(0.15, 0.0);
+        controller.update(&mut camera, &input);
+        assert!((camera.zoom - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_drag_pans_against_finger() {
+        let mut controller = TouchPanController::new(800.0);
+        let mut camera = camera2d();
+        let mut input = FakeInput::default();
+
+        input.gesture = Gesture::Drag;
+        input.drag = Vector2::new(0.1, 0.0);
+        controller.update(&mut camera, &input);
+        input.drag = Vector2::new(0.2, 0.0);
+        controller.update(&mut camera, &input);
+        assert!((camera.target.x + 160.0).abs() < 1e-3);
+        assert_eq!(camera.target.y, 0.0);
+    }
+
+    #[test]
+    fn test_edge_scroll_corner_and_center() {
+        let edge = EdgeScrollController::new(20.0, 100.0);
+        let screen = Vector2::new(800.0, 600.0);
+        let mut camera = Camera2D::<f32> {
+            zoom: 1.0,
+            ..Default::default()
+        };
+
+        let mut input = FakeInput::default();
+        input.mouse_position = Vector2::new(400.0, 300.0);
+        edge.update(&mut camera, &input, screen, None, 0.5);
+        assert_eq!(camera.target, Vector2::zeros());
+
+        // Right in the bottom right corner scrolls at full speed on both axes
+        input.mouse_position = Vector2::new(800.0, 600.0);
+        edge.update(&mut camera, &input, screen, None, 0.5);
+        assert_eq!(camera.target, Vector2::new(50.0, 50.0));
+
+        // Halfway into the margin goes half as fast
+        assert_eq!(
+            edge.scroll_direction(Vector2::new(10.0, 300.0), screen),
+            Vector2::new(-0.5, 0.0)
+        );
+
+        let bounds = Some((Vector2::new(-10.0, -10.0), Vector2::new(60.0, 60.0)));
+        edge.update(&mut camera, &input, screen, bounds, 0.5);
+        assert_eq!(camera.target, Vector2::new(60.0, 60.0));
+    }
+
+    #[test]
+    fn test_edge_scroll_3d_moves_on_ground() {
+        let edge = EdgeScrollController::new(20.0, 10.0);
+        let screen = Vector2::new(800.0, 600.0);
+        let mut camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 10.0, 0.0),
+            Vector3::new(0.0, 0.0, 10.0),
+            Vector3::y(),
+            60.0,
+        );
+        let forward = camera.forward();
+
+        // Top left corner: forward along +Z and left, which is +X when facing +Z
+        let mut input = FakeInput::default();
+        input.mouse_position = Vector2::new(0.0, 0.0);
+        edge.update_3d(&mut camera, &input, screen, None, 1.0);
+        assert!((camera.position - Vector3::new(10.0, 10.0, 10.0)).norm() < 1e-4);
+        assert!((camera.forward() - forward).norm() < 1e-5);
+
+        let bounds = BoundingBox::new(
+            Vector3::new(-5.0, 0.0, -5.0),
+            Vector3::new(15.0, 20.0, 12.0),
+        );
+        edge.update_3d(&mut camera, &input, screen, Some(&bounds), 1.0);
+        assert!((camera.position - Vector3::new(15.0, 10.0, 12.0)).norm() < 1e-4);
+    }
+
+    #[test]
+    fn test_recenter_takes_short_way() {
+        let mut orbit = OrbitController::new(Vector3::<f32>::zeros(), 10.0);
+        orbit.azimuth = 170.0;
+        orbit.elevation = 20.0;
+        // Behind this facing is an azimuth of -170°
+        let goal = deg_to_rad(-170.0f32);
+        let facing = Vector3::new(-goal.sin(), 0.0, -goal.cos());
+
+        orbit.recenter_behind(facing, 5.0, 0.1);
+        assert!(orbit.azimuth > 170.0, "should swing toward 180°");
+        for _ in 0..100 {
+            orbit.recenter_behind(facing, 5.0, 0.1);
+            assert!(
+                orbit.azimuth.abs() >= 170.0 - 1e-3,
+                "went the long way: {}",
+                orbit.azimuth
+            );
+        }
+        assert!((orbit.azimuth + 170.0).abs() < 1e-2);
+        assert_eq!(orbit.elevation, 20.0);
+    }
+
+    fn forward_of(camera: &Camera3D<f32>) -> Vector3<f32> {
+        (camera.target - camera.position).normalize()
+    }
+
+    #[test]
+    fn test_free_fly_pitch_loop() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(1.0, 2.0, 2.0),
+            Vector3::y(),
+            60.0,
+        );
+        let start = forward_of(&camera);
+        let mut fly = FreeFlyController::from_camera(&camera);
+        let mut input = FakeInput::default();
+        input.down.push(KEY_UP);
+
+        // 90 degrees per second for four seconds
+        for _ in 0..400 {
+            fly.update(&mut camera, &input, 0.01);
+        }
+        assert!((forward_of(&camera) - start).norm() < 1e-3);
+        assert!((camera.up - Vector3::y()).norm() < 1e-3);
+        assert!((camera.position - Vector3::new(1.0, 2.0, 3.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_free_fly_roll_tilts_up() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zeros(),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut fly = FreeFlyController::from_camera(&camera);
+        let mut input = FakeInput::default();
+        input.down.push(KEY_Q);
+        fly.update(&mut camera, &input, 0.5);
+
+        // Rolled 45 degrees to the left
+        assert!(camera.up.x < -0.7 && camera.up.y > 0.7);
+        assert!((forward_of(&camera) - Vector3::new(0.0, 0.0, -1.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_mouse_look_scales_with_sensitivity() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zeros(),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut input = FakeInput::default();
+        input.mouse_delta = Vector2::new(10.0, 0.0);
+
+        let mut slow = FirstPersonController::from_camera(&camera);
+        slow.update(&mut camera, &input, 0.016);
+        let (slow_yaw, _) = camera.yaw_pitch();
+
+        let mut fast = FirstPersonController::from_camera(&camera);
+        fast.yaw = 0.0;
+        fast.mouse_sensitivity = 0.2;
+        fast.update(&mut camera, &input, 0.016);
+        let (fast_yaw, _) = camera.yaw_pitch();
+
+        // Moving the mouse right turns toward -X
+        assert!((slow_yaw + 1.0).abs() < 1e-3);
+        assert!((fast_yaw + 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_mouse_look_clamps_pitch() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zeros(),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut input = FakeInput::default();
+        input.mouse_delta = Vector2::new(0.0, -5000.0);
+        let mut fps = FirstPersonController::from_camera(&camera);
+        fps.update(&mut camera, &input, 0.016);
+        assert_eq!(fps.pitch, 89.0);
+    }
+
+    #[test]
+    fn test_mouse_look_has_no_roll_drift() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(0.0, 1.7, 0.0),
+            Vector3::new(0.3, 1.9, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut fps = FirstPersonController::from_camera(&camera);
+        let mut input = FakeInput::default();
+        for frame in 0..20_000 {
+            let wiggle = (frame as f32 * 0.01).sin();
+            input.mouse_delta = Vector2::new(0.37 + wiggle * 0.2, wiggle * 0.15);
+            fps.update(&mut camera, &input, 1.0 / 144.0);
+        }
+        assert_eq!(camera.up, Vector3::y());
+        // No roll means the right vector stays level
+        assert!(camera.right().y.abs() < 1e-5);
+        assert!(fps.pitch.abs() <= fps.max_pitch);
+    }
+
+    #[test]
+    fn test_walking_keeps_target_distance() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zeros(),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut fps = FirstPersonController::from_camera(&camera);
+        let input = FakeInput {
+            down: vec![KEY_W],
+            ..Default::default()
+        };
+        for _ in 0..500 {
+            fps.update(&mut camera, &input, 1.0 / 144.0);
+            assert!(((camera.target - camera.position).norm() - 1.0).abs() < 1e-4);
+        }
+        assert!(camera.position.z > 15.0);
+        let (yaw, pitch) = camera.yaw_pitch();
+        assert!(yaw.abs() < 1e-3 && pitch.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_movement_state_from_keys() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zeros(),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut fps = FirstPersonController::from_camera(&camera);
+        let cases: &[(&[KeyboardKey], MovementState)] = &[
+            (&[], MovementState::Idle),
+            (&[KEY_W, KEY_S], MovementState::Idle),
+            (&[KEY_W], MovementState::Walking),
+            (&[KEY_S, KEY_LEFT_SHIFT], MovementState::Walking),
+            (&[KEY_W, KEY_D], MovementState::Walking),
+            (&[KEY_W, KEY_LEFT_SHIFT], MovementState::Sprinting),
+            (&[KEY_A], MovementState::Strafing),
+            (&[KEY_D, KEY_LEFT_SHIFT], MovementState::Strafing),
+            (&[KEY_LEFT_SHIFT], MovementState::Idle),
+        ];
+        for (keys, expected) in cases {
+            let input = FakeInput {
+                down: keys.to_vec(),
+                ..Default::default()
+            };
+            fps.update(&mut camera, &input, 0.016);
+            assert_eq!(fps.movement_state(), *expected, "keys {:?}", keys);
+        }
+
+        let input = FakeInput {
+            down: vec![KEY_A, KEY_S],
+            ..Default::default()
+        };
+        fps.update(&mut camera, &input, 0.016);
+        assert_eq!(fps.input_vector(), Vector2::new(-1.0, -1.0));
+    }
+
+    #[test]
+    fn test_sprint_moves_faster() {
+        let mut camera = Camera3D::perspective(
+            Vector3::zeros(),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut fps = FirstPersonController::from_camera(&camera);
+        let input = FakeInput {
+            down: vec![KEY_W, KEY_LEFT_SHIFT],
+            ..Default::default()
+        };
+        fps.update(&mut camera, &input, 1.0);
+        assert!((fps.position.z - 5.0 * 1.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_controller_blend_hands_off() {
+        let start = Camera3D::perspective(
+            Vector3::new(0.0f32, 1.0, 5.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        let walker = FirstPersonController::from_camera(&start);
+        let mut orbit = OrbitController::new(Vector3::zeros(), 10.0);
+        orbit.elevation = 30.0;
+        let input = FakeInput::default();
+        let pose = |mut controller: Box<dyn CameraController<f32>>| {
+            let mut camera = start;
+            controller.update(&mut camera, &input, 0.0);
+            camera
+        };
+        let begin = pose(Box::new(walker.clone()));
+        let end = pose(Box::new(orbit.clone()));
+
+        let mut blend = ControllerBlend::new(&start, walker, orbit, 1.0);
+        let mut camera = start;
+        blend.update(&mut camera, &input, 0.0);
+        assert!((camera.position - begin.position).norm() < 1e-5);
+        assert!((camera.target - begin.target).norm() < 1e-5);
+
+        for _ in 0..4 {
+            blend.update(&mut camera, &input, 0.25);
+        }
+        assert!(blend.finished());
+        assert!((camera.position - end.position).norm() < 1e-5);
+        assert!((camera.target - end.target).norm() < 1e-5);
+
+        // Afterwards the orbit controller drives the camera on its own
+        blend.update(&mut camera, &input, 0.25);
+        assert!((camera.position - end.position).norm() < 1e-5);
+    }
+
+    fn rail_path() -> CameraPath<f32> {
+        CameraPath::new(vec![
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 10.0),
+            Vector3::new(0.0, 1.0, 20.0),
+        ])
+    }
+
+    #[test]
+    fn test_top_down_leans_toward_cursor() {
+        let screen = Vector2::new(800.0, 600.0);
+        let player = Vector3::new(2.0f32, 0.0, 5.0);
+        let mut controller = TopDownController::new(player, 12.0);
+        let mut camera = Camera3D::perspective(Vector3::zeros(), Vector3::z(), Vector3::y(), 60.0);
+        let mut input = FakeInput::default();
+        let settle = |controller: &mut TopDownController<f32>,
+                      camera: &mut Camera3D<f32>,
+                      input: &FakeInput| {
+            for _ in 0..120 {
+                controller.update(camera, input, screen, 0.05);
+            }
+        };
+
+        input.mouse_position = screen / 2.0;
+        settle(&mut controller, &mut camera, &input);
+        assert!((camera.target - player).norm() < 1e-3);
+        assert!((camera.position.y - 12.0).abs() < 1e-4);
+        let (_, pitch) = camera.yaw_pitch();
+        assert!((pitch + 60.0).abs() < 1e-2);
+
+        // A little to the side leans the view toward the cursor without reaching the cap
+        input.mouse_position = Vector2::new(500.0, 300.0);
+        settle(&mut controller, &mut camera, &input);
+        let small = controller.aim_offset();
+        assert!(small.norm() > 0.1 && small.norm() < 4.0);
+        // Facing +Z the right of the screen is -X
+        assert!(small.x < 0.0 && small.y == 0.0);
+        assert!((camera.target - (player + small)).norm() < 1e-3);
+
+        input.mouse_position = Vector2::new(800.0, 0.0);
+        settle(&mut controller, &mut camera, &input);
+        let capped = controller.aim_offset();
+        assert!((capped.norm() - 4.0).abs() < 1e-3);
+        assert!(capped.x < small.x);
+        assert!((camera.position.y - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_rail_position_follows_path() {
+        let mut camera = Camera3D::perspective(Vector3::zeros(), Vector3::z(), Vector3::y(), 60.0);
+        let mut rail = RailController::new(rail_path(), 0.25);
+        let mut input = FakeInput::default();
+        for frame in 1..=3 {
+            // Aiming around must not change where the rail goes
+            input.mouse_delta = Vector2::new(frame as f32 * 40.0, -20.0);
+            rail.update(&mut camera, &input, 1.0);
+            assert_eq!(camera.position, rail.path.sample(frame as f32 * 0.25));
+        }
+        rail.update(&mut camera, &input, 10.0);
+        assert!(rail.finished());
+        assert!((camera.position - Vector3::new(0.0, 1.0, 20.0)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_rail_aim_is_clamped() {
+        let mut camera = Camera3D::perspective(Vector3::zeros(), Vector3::z(), Vector3::y(), 60.0);
+        let mut rail = RailController::new(rail_path(), 0.0);
+        let mut input = FakeInput::default();
+
+        input.mouse_delta = Vector2::new(-100.0, 0.0);
+        rail.update(&mut camera, &input, 0.016);
+        let (yaw, pitch) = camera.yaw_pitch();
+        assert!((yaw - 10.0).abs() < 1e-3 && pitch.abs() < 1e-3);
+
+        input.mouse_delta = Vector2::new(-10000.0, 10000.0);
+        rail.update(&mut camera, &input, 0.016);
+        assert_eq!(rail.aim(), (60.0, -45.0));
+        let (yaw, pitch) = camera.yaw_pitch();
+        assert!((yaw - 60.0).abs() < 1e-3 && (pitch + 45.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_replay_matches_recording() {
+        let start = Camera3D::perspective(
+            Vector3::new(0.0f32, 2.0, 5.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        let keys = [KEY_W, KEY_A, KEY_S, KEY_D, KEY_Q, KEY_E, KEY_UP, KEY_LEFT];
+        let mut script = crate::core::camera::InputScript::new(&keys);
+
+        let mut live = start;
+        let mut fly = FreeFlyController::from_camera(&live);
+        let mut input = FakeInput::default();
+        for frame in 0..120 {
+            input.down.clear();
+            input.down.push(if frame % 40 < 20 { KEY_W } else { KEY_A });
+            if frame % 3 == 0 {
+                input.down.push(KEY_UP);
+            }
+            input.mouse_delta = Vector2::new((frame as f32 * 0.7).sin() * 8.0, 1.5);
+            let dt = 1.0 / (50.0 + (frame % 7) as f32);
+            script.record(&input, dt);
+            fly.update(&mut live, &input, dt);
+        }
+
+        let mut replayed = start;
+        let mut fly = FreeFlyController::from_camera(&replayed);
+        for frame in script.frames() {
+            fly.update(&mut replayed, frame, frame.dt);
+        }
+        assert_eq!(script.len(), 120);
+        assert_eq!(replayed.position, live.position);
+        assert_eq!(replayed.target, live.target);
+        assert_eq!(replayed.up, live.up);
+    }
+}
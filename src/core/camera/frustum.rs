@@ -0,0 +1,207 @@
+//! View frustum tests for culling what a [`Camera3D`] can't see
+use nalgebra::{Matrix4, Scalar, Vector3, Vector4};
+use num_traits::Float;
+
+use crate::core::camera::{Camera3D, CameraLens, ViewProjection};
+use crate::core::collision::BoundingBox;
+use crate::core::math::vec3_to_f32;
+
+/// The six planes bounding what a camera can see.
+///
+/// Each plane is stored as `(a, b, c, d)` with a unit normal pointing into the frustum, so a point `p` is inside when `a*p.x + b*p.y + c*p.z + d >= 0` for all of them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Frustum {
+    planes: [Vector4<f32>; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes from a combined `projection * view` matrix.
+    pub fn from_matrix(view_projection: &Matrix4<f32>) -> Self {
+        let row = |i: usize| view_projection.row(i).transpose();
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let mut planes = [w + x, w - x, w + y, w - y, w + z, w - z];
+        for plane in planes.iter_mut() {
+            let length = plane.xyz().norm();
+            if length > f32::EPSILON {
+                *plane /= length;
+            }
+        }
+        Frustum { planes }
+    }
+
+    /// Extracts the planes from a cached [`ViewProjection`].
+    pub fn from_view_projection(view_projection: &ViewProjection) -> Self {
+        Self::from_matrix(view_projection.matrix())
+    }
+
+    /// Returns the left, right, bottom, top, near and far planes, in that order.
+    pub fn planes(&self) -> &[Vector4<f32>; 6] {
+        &self.planes
+    }
+
+    /// Returns true if `point` is inside the frustum or on its surface.
+    pub fn contains_point(&self, point: &Vector3<f32>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.xyz().dot(point) + plane.w >= 0.0)
+    }
+
+    /// Returns true if any part of `bbox` might be visible.
+    ///
+    /// A box is only rejected when it is entirely behind one of the planes, so a box just off a corner of the frustum can still pass. That is the usual trade for culling, it never hides anything that could be seen.
+    pub fn intersects_box<T>(&self, bbox: &BoundingBox<T>) -> bool
+    where
+        T: Float + Scalar,
+    {
+        let (min, max) = (vec3_to_f32(&bbox.min), vec3_to_f32(&bbox.max));
+        self.planes.iter().all(|plane| {
+            // The corner furthest along the plane's normal
+            let corner = Vector3::new(
+                if plane.x >= 0.0 { max.x } else { min.x },
+                if plane.y >= 0.0 { max.y } else { min.y },
+                if plane.z >= 0.0 { max.z } else { min.z },
+            );
+            plane.xyz().dot(&corner) + plane.w >= 0.0
+        })
+    }
+}
+
+impl<T> Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    /// Returns the camera's view frustum, using the clip planes from `lens`.
+    pub fn frustum(&self, aspect: f32, lens: &CameraLens) -> Frustum {
+        Frustum::from_view_projection(&ViewProjection::from_camera_forward_only(
+            self, aspect, lens.near, lens.far,
+        ))
+    }
+
+    /// Averages the luminance of the samples the camera can see, as a target for auto-exposure.
+    ///
+    /// Each sample is a world point with its luminance. Points outside the frustum are skipped, and the rest are weighted from 1 at the center of the screen down to 0.5 in the corners, so what the player is looking at counts most.
+    /// Returns 0 if no sample is visible.
+    pub fn visible_luminance_hint(
+        &self,
+        sample_points: &[(Vector3<T>, f32)],
+        aspect: f32,
+        lens: &CameraLens,
+    ) -> f32 {
+        let view_projection =
+            ViewProjection::from_camera_forward_only(self, aspect, lens.near, lens.far);
+        let frustum = Frustum::from_view_projection(&view_projection);
+        let (mut total, mut weights) = (0.0, 0.0);
+        for (point, luminance) in sample_points {
+            let p = vec3_to_f32(point);
+            if !frustum.contains_point(&p) {
+                continue;
+            }
+            let off_center = view_projection.project(&p).xy().norm() / std::f32::consts::SQRT_2;
+            let weight = 1.0 - 0.5 * off_center.min(1.0);
+            total += luminance * weight;
+            weights += weight;
+        }
+        if weights > 0.0 {
+            total / weights
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Passes through only the items whose box might be visible in `frustum`, see [`Frustum::intersects_box`].
+///
+/// This is lazy and doesn't allocate, so it can sit directly in a draw loop:
+/// `for (i, _) in cull(&frustum, boxes.iter().copied().enumerate()) { draw(i) }`.
+pub fn cull<'a, T, I>(
+    frustum: &'a Frustum,
+    items: I,
+) -> impl Iterator<Item = (usize, BoundingBox<T>)> + 'a
+where
+    T: Float + Scalar,
+    I: IntoIterator<Item = (usize, BoundingBox<T>)>,
+    I::IntoIter: 'a,
+{
+    items
+        .into_iter()
+        .filter(move |(_, bbox)| frustum.intersects_box(bbox))
+}
+
+#[cfg(test)]
+mod test_frustum {
+    use super::*;
+
+    fn frustum() -> Frustum {
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 0.0, 5.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        camera.frustum(1.0, &CameraLens::new(0.1, 50.0))
+    }
+
+    fn cube(center: Vector3<f32>) -> BoundingBox<f32> {
+        let half = Vector3::new(0.5, 0.5, 0.5);
+        BoundingBox::new(center - half, center + half)
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let frustum = frustum();
+        assert!(frustum.contains_point(&Vector3::zeros()));
+        assert!(!frustum.contains_point(&Vector3::new(0.0, 0.0, 6.0)));
+        assert!(!frustum.contains_point(&Vector3::new(0.0, 0.0, -50.0)));
+        assert!(!frustum.contains_point(&Vector3::new(0.0, 10.0, 0.0)));
+    }
+
+    #[test]
+    fn test_luminance_hint() {
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 0.0, 5.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        let lens = CameraLens::new(0.1, 50.0);
+        // Nine tenths of the way to the top right corner of the view at the origin's depth
+        let edge = 5.0 / 3.0f32.sqrt() * 0.9;
+        let samples = [
+            (Vector3::zeros(), 1.0),
+            (Vector3::new(edge, edge, 0.0), 3.0),
+            // Behind the camera, would pull the average way up
+            (Vector3::new(0.0, 0.0, 10.0), 100.0),
+        ];
+        let hint = camera.visible_luminance_hint(&samples, 1.0, &lens);
+        // The edge sample counts for less than the centre one, so the hint sits below the plain mean
+        let unweighted = (samples[0].1 + samples[1].1) / 2.0;
+        assert!(hint > samples[0].1 && hint < unweighted, "{}", hint);
+
+        assert_eq!(
+            camera.visible_luminance_hint(&samples[2..], 1.0, &lens),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_cull_yields_visible_indices() {
+        let frustum = frustum();
+        let boxes = [
+            cube(Vector3::zeros()),
+            // Behind the camera
+            cube(Vector3::new(0.0, 0.0, 8.0)),
+            // Past the far plane
+            cube(Vector3::new(0.0, 0.0, -60.0)),
+            // Off to the side
+            cube(Vector3::new(20.0, 0.0, 0.0)),
+            // Straddling the top edge
+            cube(Vector3::new(0.0, 5.0 / 3.0f32.sqrt(), 0.0)),
+            // Around the camera itself
+            BoundingBox::new(Vector3::new(-1.0, -1.0, 4.0), Vector3::new(1.0, 1.0, 6.0)),
+        ];
+        let visible: Vec<usize> = cull(&frustum, boxes.iter().copied().enumerate())
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(visible, vec![0, 4, 5]);
+    }
+}
@@ -0,0 +1,339 @@
+//! Input sources consumed by the camera controllers
+use std::cell::Cell;
+
+use nalgebra::Vector2;
+
+use crate::consts::{Gesture, KeyboardKey};
+#[cfg(feature = "raylib-4-0")]
+use crate::core::RaylibHandle;
+
+/// The input a camera controller reads when it updates.
+///
+/// With the `raylib-4-0` feature [`RaylibHandle`](crate::core::RaylibHandle) implements this by polling raylib directly (it needs `GetMouseDelta`). Controllers only ever see this trait, so they can be driven by anything else that implements it (recorded input, tests, networked players).
+pub trait CameraInput {
+    /// Detect if a key is being pressed.
+    fn key_down(&self, key: KeyboardKey) -> bool;
+
+    /// Detect if a key has been pressed once.
+    fn key_pressed(&self, key: KeyboardKey) -> bool;
+
+    /// Detect if a key has been released once.
+    fn key_released(&self, key: KeyboardKey) -> bool;
+
+    /// Detect if a key is NOT being pressed.
+    fn key_up(&self, key: KeyboardKey) -> bool {
+        !self.key_down(key)
+    }
+
+    /// Gets latest detected gesture.
+    fn gesture(&self) -> Gesture;
+
+    /// Gets gesture drag vector.
+    fn gesture_drag_vector(&self) -> Vector2<f32>;
+
+    /// Gets gesture pinch delta.
+    fn gesture_pinch_vector(&self) -> Vector2<f32>;
+
+    /// Returns how far the mouse moved since the last frame, in pixels.
+    fn mouse_delta(&self) -> Vector2<f32>;
+
+    /// Returns where the mouse is on the screen, in pixels from the top left.
+    fn mouse_position(&self) -> Vector2<f32>;
+
+    /// Takes the next typed character off the queue, `None` once it's empty.
+    ///
+    /// Sources without text input can leave this out.
+    fn char_pressed(&self) -> Option<char> {
+        None
+    }
+}
+
+#[cfg(feature = "raylib-4-0")]
+impl CameraInput for RaylibHandle {
+    #[inline]
+    fn key_down(&self, key: KeyboardKey) -> bool {
+        self.is_key_down(key)
+    }
+
+    #[inline]
+    fn key_pressed(&self, key: KeyboardKey) -> bool {
+        self.is_key_pressed(key)
+    }
+
+    #[inline]
+    fn key_released(&self, key: KeyboardKey) -> bool {
+        self.is_key_released(key)
+    }
+
+    #[inline]
+    fn key_up(&self, key: KeyboardKey) -> bool {
+        self.is_key_up(key)
+    }
+
+    #[inline]
+    fn gesture(&self) -> Gesture {
+        self.get_gesture_detected()
+    }
+
+    #[inline]
+    fn gesture_drag_vector(&self) -> Vector2<f32> {
+        self.get_gesture_drag_vector()
+    }
+
+    #[inline]
+    fn gesture_pinch_vector(&self) -> Vector2<f32> {
+        self.get_gesture_pinch_vector()
+    }
+
+    #[inline]
+    fn mouse_delta(&self) -> Vector2<f32> {
+        self.get_mouse_delta()
+    }
+
+    #[inline]
+    fn mouse_position(&self) -> Vector2<f32> {
+        self.get_mouse_position()
+    }
+
+    #[inline]
+    fn char_pressed(&self) -> Option<char> {
+        self.get_char_pressed()
+    }
+}
+
+/// Everything a [`CameraInput`] reported for one frame, so it can be saved and played back.
+///
+/// Keys are stored by their raylib key code, and only keys that were watched while recording are captured.
+/// Typed characters are handed out once each by [`CameraInput::char_pressed`], the same as live input.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputFrame {
+    /// Seconds since the previous frame.
+    pub dt: f32,
+    pub down: Vec<u32>,
+    pub pressed: Vec<u32>,
+    pub released: Vec<u32>,
+    pub gesture: Gesture,
+    pub drag: Vector2<f32>,
+    pub pinch: Vector2<f32>,
+    pub mouse_delta: Vector2<f32>,
+    pub mouse_position: Vector2<f32>,
+    pub chars: Vec<char>,
+    // How many of `chars` have been handed out during playback
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_char: Cell<usize>,
+}
+
+impl PartialEq for InputFrame {
+    // Playing a frame back doesn't make it a different frame
+    fn eq(&self, other: &Self) -> bool {
+        self.dt == other.dt
+            && self.down == other.down
+            && self.pressed == other.pressed
+            && self.released == other.released
+            && self.gesture == other.gesture
+            && self.drag == other.drag
+            && self.pinch == other.pinch
+            && self.mouse_delta == other.mouse_delta
+            && self.mouse_position == other.mouse_position
+            && self.chars == other.chars
+    }
+}
+
+impl InputFrame {
+    /// Takes a snapshot of `input`, checking only the keys in `keys`.
+    ///
+    /// This drains the characters typed this frame from `input`, so while recording read them back from the frame instead.
+    pub fn capture(input: &dyn CameraInput, keys: &[KeyboardKey], dt: f32) -> Self {
+        InputFrame {
+            dt,
+            down: keys
+                .iter()
+                .filter(|&&k| input.key_down(k))
+                .map(|&k| k as u32)
+                .collect(),
+            pressed: keys
+                .iter()
+                .filter(|&&k| input.key_pressed(k))
+                .map(|&k| k as u32)
+                .collect(),
+            released: keys
+                .iter()
+                .filter(|&&k| input.key_released(k))
+                .map(|&k| k as u32)
+                .collect(),
+            gesture: input.gesture(),
+            drag: input.gesture_drag_vector(),
+            pinch: input.gesture_pinch_vector(),
+            mouse_delta: input.mouse_delta(),
+            mouse_position: input.mouse_position(),
+            chars: std::iter::from_fn(|| input.char_pressed()).collect(),
+            next_char: Cell::new(0),
+        }
+    }
+
+    /// Starts handing out this frame's typed characters from the first one again, for playing a script more than once.
+    pub fn rewind_chars(&self) {
+        self.next_char.set(0);
+    }
+}
+
+impl CameraInput for InputFrame {
+    fn key_down(&self, key: KeyboardKey) -> bool {
+        self.down.contains(&(key as u32))
+    }
+
+    fn key_pressed(&self, key: KeyboardKey) -> bool {
+        self.pressed.contains(&(key as u32))
+    }
+
+    fn key_released(&self, key: KeyboardKey) -> bool {
+        self.released.contains(&(key as u32))
+    }
+
+    fn gesture(&self) -> Gesture {
+        self.gesture
+    }
+
+    fn gesture_drag_vector(&self) -> Vector2<f32> {
+        self.drag
+    }
+
+    fn gesture_pinch_vector(&self) -> Vector2<f32> {
+        self.pinch
+    }
+
+    fn mouse_delta(&self) -> Vector2<f32> {
+        self.mouse_delta
+    }
+
+    fn mouse_position(&self) -> Vector2<f32> {
+        self.mouse_position
+    }
+
+    fn char_pressed(&self) -> Option<char> {
+        let next = self.next_char.get();
+        let c = self.chars.get(next).copied()?;
+        self.next_char.set(next + 1);
+        Some(c)
+    }
+}
+
+/// A recording of per-frame input, for reproducing camera bugs and for tests.
+///
+/// Record by calling [`InputScript::record`] with the live input every frame, then feed each of [`InputScript::frames`] to the same controller (with its `dt`) to replay it.
+/// As long as the controller starts from the same state the camera follows exactly the same path.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputScript {
+    // Only needed while recording, the frames hold everything playback needs
+    #[cfg_attr(feature = "serde", serde(skip))]
+    keys: Vec<KeyboardKey>,
+    frames: Vec<InputFrame>,
+}
+
+impl InputScript {
+    /// Creates an empty script that records the given keys.
+    pub fn new(keys: &[KeyboardKey]) -> Self {
+        InputScript {
+            keys: keys.to_vec(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Records one frame of input.
+    pub fn record(&mut self, input: &dyn CameraInput, dt: f32) {
+        self.frames.push(InputFrame::capture(input, &self.keys, dt));
+    }
+
+    /// Returns the recorded frames in order.
+    pub fn frames(&self) -> &[InputFrame] {
+        &self.frames
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Hand-fed input for exercising controllers without a window.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct FakeInput {
+    pub down: Vec<KeyboardKey>,
+    pub pressed: Vec<KeyboardKey>,
+    pub released: Vec<KeyboardKey>,
+    pub gesture: Gesture,
+    pub drag: Vector2<f32>,
+    pub pinch: Vector2<f32>,
+    pub mouse_delta: Vector2<f32>,
+    pub mouse_position: Vector2<f32>,
+    pub chars: std::cell::RefCell<std::collections::VecDeque<char>>,
+}
+
+#[cfg(test)]
+impl CameraInput for FakeInput {
+    fn key_down(&self, key: KeyboardKey) -> bool {
+        self.down.contains(&key)
+    }
+
+    fn key_pressed(&self, key: KeyboardKey) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    fn key_released(&self, key: KeyboardKey) -> bool {
+        self.released.contains(&key)
+    }
+
+    fn gesture(&self) -> Gesture {
+        self.gesture
+    }
+
+    fn gesture_drag_vector(&self) -> Vector2<f32> {
+        self.drag
+    }
+
+    fn gesture_pinch_vector(&self) -> Vector2<f32> {
+        self.pinch
+    }
+
+    fn mouse_delta(&self) -> Vector2<f32> {
+        self.mouse_delta
+    }
+
+    fn mouse_position(&self) -> Vector2<f32> {
+        self.mouse_position
+    }
+
+    fn char_pressed(&self) -> Option<char> {
+        self.chars.borrow_mut().pop_front()
+    }
+}
+
+#[cfg(test)]
+mod test_input {
+    use super::*;
+    use crate::consts::KeyboardKey::*;
+
+    #[test]
+    fn test_frame_captures_key_states() {
+        let input = FakeInput {
+            down: vec![KEY_W],
+            pressed: vec![KEY_W],
+            released: vec![KEY_SPACE],
+            ..Default::default()
+        };
+        let frame = InputFrame::capture(&input, &[KEY_W, KEY_SPACE, KEY_A], 0.016);
+        assert!(frame.key_down(KEY_W) && frame.key_pressed(KEY_W) && !frame.key_up(KEY_W));
+        assert!(frame.key_released(KEY_SPACE) && frame.key_up(KEY_SPACE));
+        assert!(frame.key_up(KEY_A) && !frame.key_released(KEY_A));
+        // Keys that weren't watched are never reported
+        assert!(!frame.key_released(KEY_D) && frame.key_up(KEY_D));
+    }
+}
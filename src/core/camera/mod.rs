@@ -0,0 +1,1352 @@
+//! Utility code for using Raylib [`Camera3D`] and [`Camera2D`]
+mod camera2d;
+mod controllers;
+mod frustum;
+mod input;
+mod path;
+mod presets;
+mod projection;
+mod rig;
+
+pub use camera2d::*;
+pub use controllers::*;
+pub use frustum::*;
+pub use input::*;
+pub use path::*;
+pub use presets::*;
+pub use projection::*;
+pub use rig::*;
+
+use nalgebra::{Matrix3, Point3, Scalar, Unit, UnitQuaternion, Vector2, Vector3};
+use num_traits::Float;
+use parry3d::query::Ray;
+
+use crate::core::collision::BoundingBox;
+use crate::core::math::{
+    clamp_pitch, deg_to_rad, rad_to_deg, slerp_unit, vec3_from_f32, vec3_to_f32, Degrees,
+    FromFFIVector, Radians, ToFFIVector,
+};
+use crate::core::RaylibHandle;
+use crate::ffi;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Camera3D<T>
+where
+    T: Float,
+{
+    pub position: Vector3<T>,
+    pub target: Vector3<T>,
+    pub up: Vector3<T>,
+    pub fovy: f32,
+    type_: ffi::CameraType,
+}
+pub type Camera<T> = Camera3D<T>;
+pub type Camera3Df32 = Camera3D<f32>;
+pub type Camera3Df64 = Camera3D<f64>;
+
+impl<T> From<ffi::Camera3D> for Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    fn from(v: ffi::Camera3D) -> Camera3D<T> {
+        Camera3D {
+            position: vec3_from_f32(&Vector3::from_ffi_vector(v.position)),
+            target: vec3_from_f32(&Vector3::from_ffi_vector(v.target)),
+            up: vec3_from_f32(&Vector3::from_ffi_vector(v.up)),
+            fovy: v.fovy,
+            type_: if v.type_ == ffi::CameraType::CAMERA_ORTHOGRAPHIC as i32 {
+                ffi::CameraType::CAMERA_ORTHOGRAPHIC
+            } else {
+                ffi::CameraType::CAMERA_PERSPECTIVE
+            },
+        }
+    }
+}
+
+impl<T> Into<ffi::Camera3D> for Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    fn into(self) -> ffi::Camera3D {
+        self.to_ffi()
+    }
+}
+
+impl<T> Into<ffi::Camera3D> for &Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    fn into(self) -> ffi::Camera3D {
+        self.to_ffi()
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Camera2D<T>
+where
+    T: Float,
+{
+    pub offset: Vector2<T>,
+    pub target: Vector2<T>,
+    pub rotation: f32,
+    pub zoom: f32,
+}
+pub type Camera2Df32 = Camera2D<f32>;
+pub type Camera2Df64 = Camera2D<f64>;
+
+impl<T> From<ffi::Camera2D> for Camera2D<T>
+where
+    T: Float,
+{
+    fn from(v: ffi::Camera2D) -> Camera2D<T> {
+        unsafe { std::mem::transmute(v) }
+    }
+}
+
+impl<T> Into<ffi::Camera2D> for Camera2D<T>
+where
+    T: Float,
+{
+    fn into(self) -> ffi::Camera2D {
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<T> Into<ffi::Camera2D> for &Camera2D<T>
+where
+    T: Float,
+{
+    fn into(self) -> ffi::Camera2D {
+        ffi::Camera2D {
+            offset: self.offset.into(),
+            target: self.target.into(),
+            rotation: self.rotation,
+            zoom: self.zoom,
+        }
+    }
+}
+
+impl<T> Camera3D<T>
+where
+    T: Float,
+{
+    pub fn camera_type(&self) -> crate::consts::CameraType {
+        unsafe { std::mem::transmute(self.type_.clone()) }
+    }
+    /// Create a perspective camera.
+    /// fovy is in degrees
+    pub fn perspective(
+        position: Vector3<T>,
+        target: Vector3<T>,
+        up: Vector3<T>,
+        fovy: f32,
+    ) -> Self {
+        Camera3D {
+            position,
+            target,
+            up,
+            fovy,
+            type_: ffi::CameraType::CAMERA_PERSPECTIVE,
+        }
+    }
+    /// Create a perspective camera with the field of view given in degrees.
+    pub fn perspective_deg(
+        position: Vector3<T>,
+        target: Vector3<T>,
+        up: Vector3<T>,
+        fovy: Degrees,
+    ) -> Self {
+        Self::perspective(position, target, up, fovy.0)
+    }
+    /// Create a perspective camera with the field of view given in radians.
+    pub fn perspective_rad(
+        position: Vector3<T>,
+        target: Vector3<T>,
+        up: Vector3<T>,
+        fovy: Radians,
+    ) -> Self {
+        Self::perspective(position, target, up, Degrees::from(fovy).0)
+    }
+    /// Create a orthographic camera.
+    /// fovy is in degrees
+    pub fn orthographic(
+        position: Vector3<T>,
+        target: Vector3<T>,
+        up: Vector3<T>,
+        fovy: f32,
+    ) -> Self {
+        let mut c = Self::perspective(position, target, up, fovy);
+        c.type_ = ffi::CameraType::CAMERA_ORTHOGRAPHIC;
+        c
+    }
+}
+
+impl<T> Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    /// Converts to raylib's camera, narrowing to `f32`.
+    ///
+    /// raylib stores the projection as a plain `int`, which is the [`ffi::CameraType`] value: 0 for `CAMERA_PERSPECTIVE` and 1 for `CAMERA_ORTHOGRAPHIC`.
+    pub fn to_ffi(&self) -> ffi::Camera3D {
+        ffi::Camera3D {
+            position: vec3_to_f32(&self.position).to_ffi_vector(),
+            target: vec3_to_f32(&self.target).to_ffi_vector(),
+            up: vec3_to_f32(&self.up).to_ffi_vector(),
+            fovy: self.fovy,
+            type_: self.type_ as i32,
+        }
+    }
+
+    /// Converts the camera to another precision, such as narrowing an `f64` simulation camera to `f32` for rendering.
+    ///
+    /// The projection type and `fovy` carry over unchanged.
+    pub fn cast<U>(&self) -> Camera3D<U>
+    where
+        U: Float + Scalar,
+    {
+        let cast = |v: &Vector3<T>| v.map(|x| U::from(x).unwrap());
+        Camera3D {
+            position: cast(&self.position),
+            target: cast(&self.target),
+            up: cast(&self.up),
+            fovy: self.fovy,
+            type_: self.type_,
+        }
+    }
+
+    /// Returns the normalized direction the camera is looking in.
+    pub fn forward(&self) -> Vector3<T> {
+        vec3_from_f32(&self.basis().0)
+    }
+
+    /// Returns the normalized direction to the right of the view.
+    pub fn right(&self) -> Vector3<T> {
+        vec3_from_f32(&self.basis().1)
+    }
+
+    /// Returns the normalized up direction of the view.
+    ///
+    /// Unlike `up`, this is always perpendicular to [`Camera3D::forward`].
+    pub fn up_normalized(&self) -> Vector3<T> {
+        vec3_from_f32(&self.basis().2)
+    }
+
+    /// Returns the yaw and pitch of the view, in degrees.
+    ///
+    /// Yaw is the heading around the world Y axis, 0 looking toward +Z and 90 toward +X. Pitch is the elevation above the XZ plane, between -90 and 90.
+    /// When looking straight up or down the heading is taken from `up` instead.
+    pub fn yaw_pitch(&self) -> (T, T) {
+        let forward = self.basis().0;
+        let pitch = forward.y.max(-1.0).min(1.0).asin();
+        let yaw = if forward.x.hypot(forward.z) > 1e-6 {
+            forward.x.atan2(forward.z)
+        } else {
+            // At the poles the top of the screen points along the heading when looking down, and against it when looking up
+            let up = vec3_to_f32(&self.up);
+            if forward.y < 0.0 {
+                up.x.atan2(up.z)
+            } else {
+                (-up.x).atan2(-up.z)
+            }
+        };
+        (
+            T::from(rad_to_deg(yaw)).unwrap(),
+            T::from(rad_to_deg(pitch)).unwrap(),
+        )
+    }
+
+    /// Points the camera at the given yaw and pitch in degrees, keeping its distance to `target`.
+    ///
+    /// See [`Camera3D::yaw_pitch`] for the angle conventions. Only `target` is moved.
+    pub fn set_yaw_pitch(&mut self, yaw: T, pitch: T) {
+        let position = vec3_to_f32(&self.position);
+        let distance = (vec3_to_f32(&self.target) - position).norm();
+        let distance = if distance > f32::EPSILON {
+            distance
+        } else {
+            1.0
+        };
+        let (yaw, pitch) = (
+            deg_to_rad(yaw.to_f32().unwrap()),
+            deg_to_rad(pitch.to_f32().unwrap()),
+        );
+        let dir = Vector3::new(
+            pitch.cos() * yaw.sin(),
+            pitch.sin(),
+            pitch.cos() * yaw.cos(),
+        );
+        self.target = vec3_from_f32(&(position + dir * distance));
+    }
+
+    /// Pulls the view's pitch back into `[min_deg, max_deg]` by moving `target`, keeping the yaw and the distance to `target`.
+    ///
+    /// Useful after any manual rotation to stay clear of looking straight up or down, where the view flips, so the range never reaches past ±89 degrees. A camera already in range is left untouched.
+    /// The controllers clamp their pitch the same way.
+    pub fn clamp_pitch(&mut self, min_deg: f32, max_deg: f32) {
+        let (yaw, pitch) = self.yaw_pitch();
+        let pitch = pitch.to_f32().unwrap();
+        let clamped = clamp_pitch(pitch, min_deg, max_deg);
+        if clamped != pitch {
+            self.set_yaw_pitch(yaw, T::from(clamped).unwrap());
+        }
+    }
+
+    /// Swings the whole camera, `position` and `target` alike, around `pivot` by `yaw` and `pitch` degrees.
+    ///
+    /// Yaw turns around the world Y axis and pitch around the camera's right axis. `up` is turned along with the rest, so pitching over the top doesn't flip the view.
+    /// Unlike [`OrbitController`], what the camera looks at moves too, which suits inspecting a scene around a point other than the one in the middle of the screen.
+    pub fn orbit_around(&mut self, pivot: Vector3<T>, yaw: T, pitch: T) {
+        let right = self.basis().1;
+        let rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), deg_to_rad(yaw.to_f32().unwrap()))
+                * UnitQuaternion::from_axis_angle(
+                    &Unit::new_unchecked(right),
+                    deg_to_rad(pitch.to_f32().unwrap()),
+                );
+        let pivot = vec3_to_f32(&pivot);
+        let around = |p: &Vector3<T>| vec3_from_f32(&(pivot + rotation * (vec3_to_f32(p) - pivot)));
+        self.position = around(&self.position);
+        self.target = around(&self.target);
+        self.up = vec3_from_f32(&(rotation * vec3_to_f32(&self.up)));
+    }
+
+    /// Eases `target` toward where a moving entity will be `lead_time` seconds from now, so fast movers stay centered.
+    ///
+    /// `smoothing` is the exponential approach rate. Smoothing on its own trails a moving entity by about `1 / smoothing` seconds, so a `lead_time` of that cancels the lag.
+    /// With zero velocity this is plain smooth tracking of `target_pos`.
+    pub fn track_target(
+        &mut self,
+        target_pos: Vector3<T>,
+        target_velocity: Vector3<T>,
+        lead_time: T,
+        smoothing: f32,
+        dt: f32,
+    ) {
+        let aim =
+            vec3_to_f32(&target_pos) + vec3_to_f32(&target_velocity) * lead_time.to_f32().unwrap();
+        let target = vec3_to_f32(&self.target);
+        let t = 1.0 - (-smoothing * dt).exp();
+        self.target = vec3_from_f32(&(target + (aim - target) * t));
+    }
+
+    /// Points the camera at `target` and sets `up` to world up rolled by `roll_degrees` around the new view direction.
+    ///
+    /// `up` always ends up perpendicular to the view. Positive roll turns it toward the right of the screen, following the right hand rule around the view direction.
+    /// Looking straight up or down, the current `up` is used as the reference instead of world up.
+    pub fn look_at_with_roll(&mut self, target: Vector3<T>, roll_degrees: f32) {
+        self.target = target;
+        let forward = (vec3_to_f32(&target) - vec3_to_f32(&self.position))
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(|| Vector3::new(0.0, 0.0, -1.0));
+        let level = |reference: Vector3<f32>| {
+            (reference - forward * forward.dot(&reference)).try_normalize(1e-4)
+        };
+        let up = level(Vector3::y())
+            .or_else(|| level(vec3_to_f32(&self.up)))
+            .or_else(|| level(Vector3::z()))
+            .unwrap_or_else(Vector3::x);
+        let roll = UnitQuaternion::from_axis_angle(
+            &Unit::new_unchecked(forward),
+            deg_to_rad(roll_degrees),
+        );
+        self.up = vec3_from_f32(&(roll * up));
+    }
+
+    /// Returns a ray from `position` along the unit [`Camera3D::forward`] direction, for picking whatever is at the center of the screen.
+    ///
+    /// Rays are always `f32`, like [`RaylibHandle::get_mouse_ray`].
+    pub fn forward_ray(&self) -> Ray {
+        Ray::new(Point3::from(vec3_to_f32(&self.position)), self.basis().0)
+    }
+
+    /// Follows a vehicle at `position` from `back_distance` behind and `height` above, turning to face the way it is travelling.
+    ///
+    /// The heading swings toward `velocity` at the exponential rate `smoothing`, and the camera looks at a point `back_distance` ahead of the vehicle. Below 0.1 world units per second the current heading is kept, so a stopped vehicle doesn't send the camera spinning.
+    /// Only the horizontal part of `velocity` is used.
+    pub fn face_velocity(
+        &mut self,
+        position: Vector3<T>,
+        velocity: Vector3<T>,
+        back_distance: T,
+        height: T,
+        smoothing: f32,
+        dt: f32,
+    ) {
+        let flat = |v: Vector3<f32>| Vector3::new(v.x, 0.0, v.z).try_normalize(1e-6);
+        let current = flat(self.basis().0).unwrap_or_else(Vector3::z);
+        let velocity = vec3_to_f32(&velocity);
+        let heading = match flat(velocity) {
+            Some(desired) if velocity.xz().norm() >= 0.1 => {
+                slerp_unit(current, desired, 1.0 - (-smoothing * dt).exp())
+            }
+            _ => current,
+        };
+
+        let position = vec3_to_f32(&position);
+        let back = back_distance.to_f32().unwrap();
+        self.position =
+            vec3_from_f32(&(position - heading * back + Vector3::y() * height.to_f32().unwrap()));
+        self.target = vec3_from_f32(&(position + heading * back));
+        self.up = Vector3::new(T::zero(), T::one(), T::zero());
+    }
+
+    /// Returns where the view direction meets the horizontal plane at height `plane_y`, for finding what an RTS camera is looking at.
+    ///
+    /// Returns `None` if the camera looks parallel to or away from the plane.
+    pub fn ground_focus_point(&self, plane_y: T) -> Option<Vector3<T>> {
+        let forward = self.basis().0;
+        let position = vec3_to_f32(&self.position);
+        if forward.y.abs() < f32::EPSILON {
+            return None;
+        }
+        let t = (plane_y.to_f32().unwrap() - position.y) / forward.y;
+        if t < 0.0 {
+            return None;
+        }
+        let mut point = position + forward * t;
+        // Land exactly on the plane rather than a rounding error away from it
+        point.y = plane_y.to_f32().unwrap();
+        Some(vec3_from_f32(&point))
+    }
+
+    /// Keeps `position` inside `bbox`, moving `target` along with it so the view direction doesn't change.
+    ///
+    /// Call this after a free-fly controller moves the camera to confine it to a work volume.
+    pub fn clamp_position_to_box(&mut self, bbox: &BoundingBox<T>) {
+        let position = vec3_to_f32(&self.position);
+        let (min, max) = (vec3_to_f32(&bbox.min), vec3_to_f32(&bbox.max));
+        let clamped = Vector3::new(
+            position.x.max(min.x).min(max.x),
+            position.y.max(min.y).min(max.y),
+            position.z.max(min.z).min(max.z),
+        );
+        let shift = clamped - position;
+        self.position = vec3_from_f32(&clamped);
+        self.target = vec3_from_f32(&(vec3_to_f32(&self.target) + shift));
+    }
+
+    /// Copies everything about the camera, including its projection type, for undo stacks and the like.
+    pub fn snapshot(&self) -> CameraSnapshot<T> {
+        CameraSnapshot {
+            position: self.position,
+            target: self.target,
+            up: self.up,
+            fovy: self.fovy,
+            camera_type: self.type_,
+        }
+    }
+
+    /// Puts the camera back exactly as it was when `snap` was taken.
+    pub fn restore(&mut self, snap: &CameraSnapshot<T>) {
+        self.position = snap.position;
+        self.target = snap.target;
+        self.up = snap.up;
+        self.fovy = snap.fovy;
+        self.type_ = snap.camera_type;
+    }
+
+    /// Zooms an orthographic camera by scaling the height of its view, `fovy`, by `factor` and keeping it within `[min_extent, max_extent]` world units.
+    ///
+    /// A factor below 1 zooms in. Perspective cameras are left alone, their zoom is a field of view change instead.
+    pub fn ortho_zoom(&mut self, factor: f32, min_extent: f32, max_extent: f32) {
+        if self.type_ == ffi::CameraType::CAMERA_ORTHOGRAPHIC {
+            self.fovy = (self.fovy * factor).max(min_extent).min(max_extent);
+        }
+    }
+
+    /// Moves the camera `amount` along its view direction, positive toward `target`, keeping its distance from `target` within `[min_distance, max_distance]`.
+    ///
+    /// Unlike moving `position` directly this can never pass through the target and flip the view around. `target` stays where it is.
+    pub fn dolly_clamped(&mut self, amount: T, min_distance: T, max_distance: T) {
+        let forward = self.basis().0;
+        let distance = (vec3_to_f32(&self.target) - vec3_to_f32(&self.position)).norm();
+        let distance = (distance - amount.to_f32().unwrap())
+            .max(min_distance.to_f32().unwrap())
+            .min(max_distance.to_f32().unwrap());
+        self.position = vec3_from_f32(&(vec3_to_f32(&self.target) - forward * distance));
+    }
+
+    /// Sets `fovy` to `new_fovy` until the returned guard is dropped, which puts the old value back.
+    ///
+    /// The camera can still be read and changed through the guard. Because the restore happens on drop, an early return can't leave the FOV stuck at the override.
+    pub fn fov_override(&mut self, new_fovy: f32) -> FovGuard<'_, T> {
+        let previous = self.fovy;
+        self.fovy = new_fovy;
+        FovGuard {
+            camera: self,
+            previous,
+        }
+    }
+
+    /// Returns a camera 3 units from the origin looking at it, turned the same way as this one.
+    ///
+    /// Only the orientation carries over, for drawing an axis gizmo in a corner viewport that turns with the main view.
+    pub fn orientation_only(&self) -> Camera3D<T> {
+        let (forward, _, up) = self.basis();
+        Camera3D {
+            position: vec3_from_f32(&(forward * -3.0)),
+            target: Vector3::new(T::zero(), T::zero(), T::zero()),
+            up: vec3_from_f32(&up),
+            fovy: self.fovy,
+            type_: self.type_,
+        }
+    }
+
+    /// Returns the camera's orientation without its position, with the right, up and backward (`-forward`) vectors as columns.
+    ///
+    /// It takes camera space to world space, so `-Z` maps to the view direction. The transpose is the rotation part of the view matrix, for billboards and gizmos.
+    pub fn rotation_matrix(&self) -> Matrix3<f32> {
+        let (forward, right, up) = self.basis();
+        Matrix3::from_columns(&[right, up, -forward])
+    }
+
+    /// Returns the camera as a glTF node transform: translation, rotation quaternion as `[x, y, z, w]`, and `yfov` in radians.
+    ///
+    /// A glTF camera looks down its local -Z axis with +Y up, so the rotation takes -Z to the direction from `position` to `target` and +Y to `up` (made perpendicular to that direction).
+    /// An unrotated camera looking down -Z with +Y up gives the identity quaternion. For orthographic cameras the last value is still `fovy` converted to radians, use the view height for glTF's `ymag` instead.
+    pub fn to_gltf_transform(&self) -> ([f32; 3], [f32; 4], f32) {
+        let (forward, _, up) = self.basis();
+        let position = vec3_to_f32(&self.position);
+        // face_towards points local +Z along the direction given, and glTF cameras look down -Z
+        let rotation = UnitQuaternion::face_towards(&-forward, &up);
+        (
+            [position.x, position.y, position.z],
+            [rotation.i, rotation.j, rotation.k, rotation.w],
+            deg_to_rad(self.fovy),
+        )
+    }
+
+    /// Rebuilds `up` as a unit vector at right angles to the view direction, keeping `position` and `target`.
+    ///
+    /// Rounding errors from many small rotations let `up` drift off unit length or toward the view direction, which skews the view matrix. Controllers that accumulate rotations should call this every so often.
+    /// If `up` is nearly parallel to the view it carries no roll information, and world up is used instead, or world +Z when looking straight up or down.
+    pub fn reorthonormalize(&mut self) {
+        let forward = self.basis().0;
+        let right = forward
+            .cross(&vec3_to_f32(&self.up))
+            .try_normalize(1e-3)
+            .unwrap_or_else(|| {
+                let reference = if forward.y.abs() < 0.99 {
+                    Vector3::y()
+                } else {
+                    Vector3::z()
+                };
+                forward.cross(&reference).normalize()
+            });
+        self.up = vec3_from_f32(&right.cross(&forward).normalize());
+    }
+
+    /// Returns the forward, right and up vectors of the view as an orthonormal basis.
+    pub(crate) fn basis(&self) -> (Vector3<f32>, Vector3<f32>, Vector3<f32>) {
+        let forward = (vec3_to_f32(&self.target) - vec3_to_f32(&self.position))
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(|| Vector3::new(0.0, 0.0, -1.0));
+        let right = forward
+            .cross(&vec3_to_f32(&self.up))
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::x);
+        let up = right.cross(&forward);
+        (forward, right, up)
+    }
+}
+
+/// A saved copy of a [`Camera3D`], see [`Camera3D::snapshot`].
+///
+/// Unlike [`Camera3D`] every field is public, including the projection type.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CameraSnapshot<T>
+where
+    T: Scalar,
+{
+    pub position: Vector3<T>,
+    pub target: Vector3<T>,
+    pub up: Vector3<T>,
+    pub fovy: f32,
+    pub camera_type: crate::consts::CameraType,
+}
+
+/// Restores a camera's `fovy` when dropped, see [`Camera3D::fov_override`].
+#[derive(Debug)]
+pub struct FovGuard<'a, T>
+where
+    T: Float,
+{
+    camera: &'a mut Camera3D<T>,
+    previous: f32,
+}
+
+impl<'a, T> FovGuard<'a, T>
+where
+    T: Float,
+{
+    /// Returns the `fovy` that will be restored.
+    pub fn previous(&self) -> f32 {
+        self.previous
+    }
+}
+
+impl<'a, T> std::ops::Deref for FovGuard<'a, T>
+where
+    T: Float,
+{
+    type Target = Camera3D<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.camera
+    }
+}
+
+impl<'a, T> std::ops::DerefMut for FovGuard<'a, T>
+where
+    T: Float,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.camera
+    }
+}
+
+impl<'a, T> Drop for FovGuard<'a, T>
+where
+    T: Float,
+{
+    fn drop(&mut self) {
+        self.camera.fovy = self.previous;
+    }
+}
+
+/// Blends between two camera poses, `t = 0` giving `a` and `t = 1` giving `b`.
+///
+/// Positions and `fovy` are lerped. The view direction and `up` go through [`slerp_unit`] so the camera turns smoothly instead of cutting the corner, and the target stays at the lerped distance along the blended direction.
+/// The projection type switches from `a`'s to `b`'s halfway through.
+pub fn lerp_cameras<T>(a: &Camera3D<T>, b: &Camera3D<T>, t: f32) -> Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    let (pa, pb) = (vec3_to_f32(&a.position), vec3_to_f32(&b.position));
+    let (fa, _, ua) = a.basis();
+    let (fb, _, ub) = b.basis();
+    let da = (vec3_to_f32(&a.target) - pa).norm();
+    let db = (vec3_to_f32(&b.target) - pb).norm();
+
+    let position = pa + (pb - pa) * t;
+    let forward = slerp_unit(fa, fb, t);
+    Camera3D {
+        position: vec3_from_f32(&position),
+        target: vec3_from_f32(&(position + forward * (da + (db - da) * t))),
+        up: vec3_from_f32(&slerp_unit(ua, ub, t)),
+        fovy: a.fovy + (b.fovy - a.fovy) * t,
+        type_: if t < 0.5 { a.type_ } else { b.type_ },
+    }
+}
+
+/// Keeps the last two simulation poses of a camera, for rendering smoothly with a fixed physics timestep.
+///
+/// [`CameraInterpolator::push`] the simulated camera after every physics step, then render with [`CameraInterpolator::interpolated`], passing how far the render time is between the last two steps.
+#[derive(Debug, Copy, Clone)]
+pub struct CameraInterpolator<T>
+where
+    T: Float,
+{
+    previous: Camera3D<T>,
+    current: Camera3D<T>,
+}
+
+impl<T> CameraInterpolator<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates an interpolator at rest at `camera`.
+    pub fn new(camera: Camera3D<T>) -> Self {
+        CameraInterpolator {
+            previous: camera,
+            current: camera,
+        }
+    }
+
+    /// Records the pose after a simulation step.
+    pub fn push(&mut self, camera: Camera3D<T>) {
+        self.previous = self.current;
+        self.current = camera;
+    }
+
+    /// Returns the pose before the last step.
+    pub fn previous(&self) -> &Camera3D<T> {
+        &self.previous
+    }
+
+    /// Returns the pose after the last step.
+    pub fn current(&self) -> &Camera3D<T> {
+        &self.current
+    }
+
+    /// Returns the pose `alpha` of the way from the previous step to the current one, see [`lerp_cameras`].
+    pub fn interpolated(&self, alpha: f32) -> Camera3D<T> {
+        lerp_cameras(&self.previous, &self.current, alpha.max(0.0).min(1.0))
+    }
+}
+
+impl RaylibHandle {
+    /// Sets camera mode.
+    #[inline]
+    pub fn set_camera_mode(
+        &mut self,
+        camera: impl Into<ffi::Camera3D>,
+        mode: crate::consts::CameraMode,
+    ) {
+        unsafe {
+            ffi::SetCameraMode(camera.into(), mode as i32);
+        }
+    }
+
+    /// Updates camera position for selected mode.
+    #[inline]
+    pub fn update_camera<T>(&self, camera: &mut Camera3D<T>)
+    where
+        T: Float + Scalar,
+    {
+        unsafe {
+            let mut fficam: ffi::Camera3D = (*camera).into();
+            ffi::UpdateCamera(&mut fficam);
+            *camera = fficam.into();
+        }
+    }
+
+    /// Returns the camera's forward vector (normalized), as computed by raylib.
+    ///
+    /// `GetCameraForward` was added in raylib 4.5, so this needs the `raylib-4-5` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-5")]
+    pub fn get_camera_forward<T>(&self, camera: &Camera3D<T>) -> Vector3<T>
+    where
+        T: Float + Scalar,
+    {
+        let mut fficam: ffi::Camera3D = camera.into();
+        vec3_from_f32(&Vector3::from_ffi_vector(unsafe {
+            ffi::GetCameraForward(&mut fficam)
+        }))
+    }
+
+    /// Returns the camera's up vector (normalized), as computed by raylib.
+    ///
+    /// This is `up` normalized, it is only perpendicular to the forward vector if `up` already was.
+    /// Needs raylib 4.5 and the `raylib-4-5` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-5")]
+    pub fn get_camera_up<T>(&self, camera: &Camera3D<T>) -> Vector3<T>
+    where
+        T: Float + Scalar,
+    {
+        let mut fficam: ffi::Camera3D = camera.into();
+        vec3_from_f32(&Vector3::from_ffi_vector(unsafe {
+            ffi::GetCameraUp(&mut fficam)
+        }))
+    }
+
+    /// Returns the camera's right vector (normalized), as computed by raylib.
+    ///
+    /// Needs raylib 4.5 and the `raylib-4-5` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-5")]
+    pub fn get_camera_right<T>(&self, camera: &Camera3D<T>) -> Vector3<T>
+    where
+        T: Float + Scalar,
+    {
+        let mut fficam: ffi::Camera3D = camera.into();
+        vec3_from_f32(&Vector3::from_ffi_vector(unsafe {
+            ffi::GetCameraRight(&mut fficam)
+        }))
+    }
+
+    /// Sets camera pan key to combine with mouse movement (free camera).
+    #[inline]
+    pub fn set_camera_pan_control(&mut self, pan_key: crate::consts::KeyboardKey) {
+        unsafe {
+            ffi::SetCameraPanControl(pan_key as i32);
+        }
+    }
+
+    /// Sets camera alt key to combine with mouse movement (free camera).
+    #[inline]
+    pub fn set_camera_alt_control(&mut self, alt_key: crate::consts::KeyboardKey) {
+        unsafe {
+            ffi::SetCameraAltControl(alt_key as i32);
+        }
+    }
+
+    /// Sets camera smooth zoom key to combine with mouse (free camera).
+    #[inline]
+    pub fn set_camera_smooth_zoom_control(&mut self, sz_key: crate::consts::KeyboardKey) {
+        unsafe {
+            ffi::SetCameraSmoothZoomControl(sz_key as i32);
+        }
+    }
+
+    /// Sets camera move controls (1st person and 3rd person cameras).
+    #[inline]
+    pub fn set_camera_move_controls(
+        &mut self,
+        front_key: crate::consts::KeyboardKey,
+        back_key: crate::consts::KeyboardKey,
+        right_key: crate::consts::KeyboardKey,
+        left_key: crate::consts::KeyboardKey,
+        up_key: crate::consts::KeyboardKey,
+        down_key: crate::consts::KeyboardKey,
+    ) {
+        unsafe {
+            ffi::SetCameraMoveControls(
+                front_key as i32,
+                back_key as i32,
+                right_key as i32,
+                left_key as i32,
+                up_key as i32,
+                down_key as i32,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_camera {
+    use super::*;
+
+    #[test]
+    fn test_yaw_pitch_round_trip() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(1.0f32, 2.0, 3.0),
+            Vector3::new(-4.0, 0.5, 7.0),
+            Vector3::y(),
+            60.0,
+        );
+        let target = camera.target;
+        let (yaw, pitch) = camera.yaw_pitch();
+        camera.set_yaw_pitch(yaw, pitch);
+        assert!((camera.target - target).norm() < 1e-4);
+
+        for &(yaw, pitch) in &[(0.0, 0.0), (90.0, 30.0), (-135.0, -60.0), (179.0, 80.0)] {
+            camera.set_yaw_pitch(yaw, pitch);
+            let (y, p) = camera.yaw_pitch();
+            assert!((y - yaw).abs() < 1e-3 && (p - pitch).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_yaw_pitch_at_pole() {
+        // Looking straight down with the top of the screen toward +X
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 10.0, 0.0),
+            Vector3::zeros(),
+            Vector3::x(),
+            60.0,
+        );
+        let (yaw, pitch) = camera.yaw_pitch();
+        assert!((yaw - 90.0).abs() < 1e-3);
+        assert!((pitch + 90.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_clamp_pitch() {
+        let mut camera = Camera3D::perspective(Vector3::zeros(), Vector3::z(), Vector3::y(), 60.0);
+        camera.set_yaw_pitch(30.0f32, 88.0);
+        camera.clamp_pitch(-85.0, 85.0);
+        let (yaw, pitch) = camera.yaw_pitch();
+        assert!((pitch - 85.0).abs() < 1e-3 && (yaw - 30.0).abs() < 1e-3);
+        assert!((camera.target.norm() - 1.0).abs() < 1e-5);
+
+        camera.set_yaw_pitch(-120.0, 40.0);
+        let before = camera.target;
+        camera.clamp_pitch(-85.0, 85.0);
+        assert_eq!(camera.target, before);
+    }
+
+    #[test]
+    fn test_ground_focus_point() {
+        let down = Camera3D::perspective(
+            Vector3::new(0.0f32, 10.0, 0.0),
+            Vector3::new(0.0, 5.0, 5.0),
+            Vector3::y(),
+            60.0,
+        );
+        let point = down.ground_focus_point(0.0).unwrap();
+        assert!((point - Vector3::new(0.0, 0.0, 10.0)).norm() < 1e-4);
+
+        let up = Camera3D::perspective(
+            Vector3::new(0.0f32, 10.0, 0.0),
+            Vector3::new(0.0, 15.0, 5.0),
+            Vector3::y(),
+            60.0,
+        );
+        assert_eq!(up.ground_focus_point(0.0), None);
+    }
+
+    #[test]
+    fn test_track_target_lead() {
+        let (smoothing, dt) = (4.0f32, 1.0 / 60.0);
+        // The exact lag of the per-frame smoothing, which tends to 1 / smoothing
+        let a = 1.0 - (-smoothing * dt).exp();
+        let lead = dt * (1.0 - a) / a;
+        let velocity = Vector3::new(3.0f32, 0.0, -1.0);
+
+        let mut camera =
+            Camera3D::perspective(Vector3::y() * 5.0, Vector3::zeros(), Vector3::z(), 60.0);
+        let mut lagging = camera;
+        let mut entity = Vector3::zeros();
+        for _ in 0..600 {
+            entity += velocity * dt;
+            camera.track_target(entity, velocity, lead, smoothing, dt);
+            lagging.track_target(entity, velocity, 0.0, smoothing, dt);
+        }
+        assert!((camera.target - entity).norm() < 1e-3);
+        assert!((lagging.target - entity).norm() > 0.5);
+    }
+
+    #[test]
+    fn test_track_target_still() {
+        let mut camera =
+            Camera3D::perspective(Vector3::y() * 5.0, Vector3::zeros(), Vector3::z(), 60.0);
+        let entity = Vector3::new(2.0f32, 0.0, 7.0);
+        for _ in 0..300 {
+            camera.track_target(entity, Vector3::zeros(), 0.5, 4.0, 1.0 / 60.0);
+        }
+        assert!((camera.target - entity).norm() < 1e-3);
+    }
+
+    #[test]
+    fn test_to_ffi_projection() {
+        let perspective = Camera3D::perspective(
+            Vector3::new(1.0f64, 2.0, 3.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            45.0,
+        );
+        let ffi_cam = perspective.to_ffi();
+        assert_eq!(ffi_cam.type_, 0);
+        assert_eq!(
+            (ffi_cam.position.x, ffi_cam.position.y, ffi_cam.position.z),
+            (1.0, 2.0, 3.0)
+        );
+        assert_eq!(ffi_cam.fovy, 45.0);
+
+        let orthographic = Camera3D::orthographic(
+            perspective.position,
+            perspective.target,
+            perspective.up,
+            10.0,
+        );
+        assert_eq!(orthographic.to_ffi().type_, 1);
+
+        let back: Camera3D<f64> = orthographic.to_ffi().into();
+        assert_eq!(
+            back.camera_type() as u32,
+            crate::consts::CameraType::CAMERA_ORTHOGRAPHIC as u32
+        );
+        assert_eq!(back.position, perspective.position);
+    }
+
+    #[test]
+    fn test_fovy_units() {
+        let (position, target) = (Vector3::new(0.0f32, 1.0, 5.0), Vector3::zeros());
+        let deg = Camera3D::perspective_deg(position, target, Vector3::y(), Degrees(90.0));
+        let rad = Camera3D::perspective_rad(
+            position,
+            target,
+            Vector3::y(),
+            Radians(std::f32::consts::FRAC_PI_2),
+        );
+        assert!((deg.fovy - rad.fovy).abs() < 1e-5);
+        assert_eq!(deg.fovy, 90.0);
+        assert_eq!(
+            (deg.position, deg.target, deg.up),
+            (rad.position, rad.target, rad.up)
+        );
+        assert!((Radians::from(Degrees(180.0)).0 - std::f32::consts::PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_orbit_around_full_turn() {
+        let start = Camera3D::perspective(
+            Vector3::new(4.0f32, 2.0, 6.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let pivot = Vector3::new(-2.0, 1.0, 3.0);
+
+        let mut camera = start;
+        for _ in 0..12 {
+            camera.orbit_around(pivot, 30.0, 0.0);
+        }
+        assert!((camera.position - start.position).norm() < 1e-4);
+        assert!((camera.target - start.target).norm() < 1e-4);
+
+        for _ in 0..12 {
+            camera.orbit_around(pivot, 0.0, 30.0);
+        }
+        assert!((camera.position - start.position).norm() < 1e-4);
+        assert!((camera.target - start.target).norm() < 1e-4);
+        assert!((camera.up - start.up).norm() < 1e-4);
+    }
+
+    #[test]
+    fn test_look_at_with_roll() {
+        let mut camera = Camera3D::perspective(Vector3::zeros(), Vector3::z(), Vector3::x(), 60.0);
+        let target = Vector3::new(3.0f32, 1.0, -4.0);
+        camera.look_at_with_roll(target, 0.0);
+        assert_eq!(camera.target, target);
+        let level = camera.up;
+        assert!(level.dot(&camera.forward()).abs() < 1e-6);
+        assert!((level - camera.up_normalized()).norm() < 1e-6);
+        assert!(level.y > 0.9 && camera.right().y.abs() < 1e-6);
+
+        camera.look_at_with_roll(target, 180.0);
+        assert!((camera.up + level).norm() < 1e-5);
+
+        camera.look_at_with_roll(target, 90.0);
+        let right = Camera3D::perspective(Vector3::zeros(), target, level, 60.0).right();
+        assert!((camera.up - right).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_face_velocity() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 2.0, -6.0),
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut car = Vector3::zeros();
+        let velocity = Vector3::new(20.0, 0.0, 0.0);
+        for _ in 0..300 {
+            car += velocity / 60.0;
+            camera.face_velocity(car, velocity, 6.0, 2.0, 8.0, 1.0 / 60.0);
+        }
+        assert!((camera.position - (car + Vector3::new(-6.0, 2.0, 0.0))).norm() < 1e-3);
+        assert!(camera.forward().x > 0.9);
+
+        // Parked, with a little jitter in the velocity
+        let before = camera.forward();
+        for i in 0..300 {
+            let jitter = Vector3::new(0.0, 0.0, if i % 2 == 0 { 0.05 } else { -0.05 });
+            camera.face_velocity(car, jitter, 6.0, 2.0, 8.0, 1.0 / 60.0);
+        }
+        assert!((camera.forward() - before).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_forward_ray() {
+        let camera = Camera3D::perspective(
+            Vector3::new(1.0f32, 2.0, 3.0),
+            Vector3::new(4.0, -2.0, 3.0),
+            Vector3::y(),
+            60.0,
+        );
+        let ray = camera.forward_ray();
+        assert_eq!(ray.origin, Point3::new(1.0, 2.0, 3.0));
+        assert!((ray.dir - camera.forward()).norm() < 1e-6);
+        assert!((ray.dir.norm() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_clamp_position_to_box() {
+        let bbox = BoundingBox::new(Vector3::new(-5.0, 0.0, -5.0), Vector3::new(5.0, 10.0, 5.0));
+        let mut camera = Camera3D::perspective(
+            Vector3::new(8.0f32, 2.0, 0.0),
+            Vector3::new(9.0, 1.0, 1.0),
+            Vector3::y(),
+            60.0,
+        );
+        let forward = camera.forward();
+        camera.clamp_position_to_box(&bbox);
+        assert_eq!(camera.position, Vector3::new(5.0, 2.0, 0.0));
+        assert_eq!(camera.target, Vector3::new(6.0, 1.0, 1.0));
+        assert!((camera.forward() - forward).norm() < 1e-6);
+
+        // Already inside, nothing moves
+        let before = camera;
+        camera.clamp_position_to_box(&bbox);
+        assert_eq!(camera.position, before.position);
+        assert_eq!(camera.target, before.target);
+    }
+
+    #[test]
+    fn test_orientation_only() {
+        let camera = Camera3D::perspective(
+            Vector3::new(40.0f32, 25.0, -12.0),
+            Vector3::new(38.0, 24.0, -10.0),
+            Vector3::y(),
+            45.0,
+        );
+        let gizmo = camera.orientation_only();
+        assert!((gizmo.forward() - camera.forward()).norm() < 1e-6);
+        assert!((gizmo.up_normalized() - camera.up_normalized()).norm() < 1e-6);
+        assert_eq!(gizmo.target, Vector3::zeros());
+        assert!((gizmo.position.norm() - 3.0).abs() < 1e-5);
+        assert_eq!(gizmo.fovy, 45.0);
+    }
+
+    #[test]
+    fn test_rotation_matrix() {
+        let camera = Camera3D::perspective(
+            Vector3::new(2.0f32, 5.0, -1.0),
+            Vector3::new(-3.0, 0.5, 4.0),
+            Vector3::y(),
+            60.0,
+        );
+        let rotation = camera.rotation_matrix();
+        assert!((rotation.transpose() * rotation - Matrix3::identity()).amax() < 1e-5);
+        assert!((rotation * Vector3::new(0.0, 0.0, -1.0) - camera.forward()).norm() < 1e-6);
+        let view = camera
+            .get_view_matrix()
+            .fixed_slice::<3, 3>(0, 0)
+            .into_owned();
+        assert!((view - rotation.transpose()).amax() < 1e-5);
+    }
+
+    #[test]
+    fn test_gltf_transform() {
+        let camera = Camera3D::perspective(
+            Vector3::new(1.0f32, 2.0, 3.0),
+            Vector3::new(1.0, 2.0, -7.0),
+            Vector3::y(),
+            60.0,
+        );
+        let (translation, rotation, yfov) = camera.to_gltf_transform();
+        assert_eq!(translation, [1.0, 2.0, 3.0]);
+        assert!((rotation[3].abs() - 1.0).abs() < 1e-6);
+        assert!(rotation[..3].iter().all(|c| c.abs() < 1e-6));
+        assert!((yfov - std::f32::consts::FRAC_PI_3).abs() < 1e-6);
+
+        // Facing +X, the camera's local -Z has to end up along +X
+        let camera = Camera3D::perspective(Vector3::zeros(), Vector3::x(), Vector3::y(), 60.0);
+        let (_, [x, y, z, w], _) = camera.to_gltf_transform();
+        let rotation = UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(w, x, y, z));
+        assert!((rotation * Vector3::new(0.0, 0.0, -1.0) - Vector3::x()).norm() < 1e-6);
+        assert!((rotation * Vector3::y() - Vector3::y()).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_interpolator_blends_steps() {
+        let start = Camera3D::perspective(
+            Vector3::new(0.0f32, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, -5.0),
+            Vector3::y(),
+            60.0,
+        );
+        let mut interpolator = CameraInterpolator::new(start);
+        let mut moved = start;
+        moved.position.x += 2.0;
+        moved.target.x += 2.0;
+        moved.fovy = 70.0;
+        interpolator.push(moved);
+
+        let close = |a: Vector3<f32>, b: Vector3<f32>| (a - b).norm() < 1e-5;
+        let at_zero = interpolator.interpolated(0.0);
+        assert!(close(at_zero.position, start.position) && close(at_zero.target, start.target));
+        let at_one = interpolator.interpolated(1.0);
+        assert!(close(at_one.position, moved.position) && close(at_one.target, moved.target));
+        let half = interpolator.interpolated(0.5);
+        assert!(close(half.position, Vector3::new(1.0, 1.0, 0.0)));
+        assert!(close(half.target, Vector3::new(1.0, 1.0, -5.0)));
+        assert!(close(half.up, Vector3::y()));
+        assert!((half.fovy - 65.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut camera = Camera3D::orthographic(
+            Vector3::new(1.5f64, 2.25, -3.125),
+            Vector3::new(0.1, 0.2, 0.3),
+            Vector3::new(0.0, 1.0, 0.01),
+            12.5,
+        );
+        let snap = camera.snapshot();
+        camera.position.x += 10.0;
+        camera.target = Vector3::zeros();
+        camera.up = Vector3::x();
+        camera.fovy = 90.0;
+        camera.type_ = ffi::CameraType::CAMERA_PERSPECTIVE;
+
+        camera.restore(&snap);
+        assert_eq!(camera.snapshot(), snap);
+        assert_eq!(camera.position, Vector3::new(1.5, 2.25, -3.125));
+        assert_eq!(camera.camera_type(), ffi::CameraType::CAMERA_ORTHOGRAPHIC);
+    }
+
+    #[test]
+    fn test_reorthonormalize() {
+        let mut camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 1.0, 5.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.05, 1.3, -0.02),
+            60.0,
+        );
+        let forward = camera.forward();
+        let right = forward.cross(&camera.up).normalize();
+        camera.reorthonormalize();
+        assert!((camera.up.norm() - 1.0).abs() < 1e-6);
+        assert!(camera.up.dot(&forward).abs() < 1e-6);
+        // Still rolled the same way, and the view direction is untouched
+        assert!(camera.up.dot(&right).abs() < 1e-6);
+        assert_eq!(camera.forward(), forward);
+
+        for &dir in [Vector3::x(), Vector3::y(), -Vector3::y()].iter() {
+            let mut parallel = Camera3D::perspective(Vector3::zeros(), dir * 3.0, dir * 2.0, 60.0);
+            parallel.reorthonormalize();
+            assert!(parallel.up.iter().all(|x| x.is_finite()));
+            assert!((parallel.up.norm() - 1.0).abs() < 1e-6);
+            assert!(parallel.up.dot(&dir).abs() < 1e-6);
+        }
+        let mut level = Camera3D::perspective(Vector3::zeros(), Vector3::x(), Vector3::x(), 60.0);
+        level.reorthonormalize();
+        assert!((level.up - Vector3::y()).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_cast_round_trip() {
+        let camera: Camera3Df64 = Camera3D::orthographic(
+            Vector3::new(1234.5678901, -2.000001, 0.1),
+            Vector3::new(3.0, 4.0, 5.0),
+            Vector3::y(),
+            24.0,
+        );
+        let narrow: Camera3Df32 = camera.cast();
+        let back: Camera3Df64 = narrow.cast();
+        let close = |a: Vector3<f64>, b: Vector3<f64>| {
+            (a - b).iter().all(|d| d.abs() <= 1e-6 * a.amax().max(1.0))
+        };
+        assert!(close(back.position, camera.position));
+        assert!(close(back.target, camera.target));
+        assert!(close(back.up, camera.up));
+        assert_eq!(back.fovy, 24.0);
+        assert_eq!(narrow.camera_type(), ffi::CameraType::CAMERA_ORTHOGRAPHIC);
+        assert_eq!(back.camera_type(), ffi::CameraType::CAMERA_ORTHOGRAPHIC);
+        assert_ne!(
+            back.position, camera.position,
+            "f32 can't hold this exactly"
+        );
+
+        let flat: Camera2Df64 = Camera2D {
+            target: Vector2::new(0.25, -8.5),
+            zoom: 2.0,
+            ..Default::default()
+        };
+        let narrow: Camera2Df32 = flat.cast();
+        assert_eq!(narrow.target, Vector2::new(0.25f32, -8.5));
+        assert_eq!(narrow.zoom, 2.0);
+    }
+
+    #[test]
+    fn test_ortho_zoom() {
+        let mut camera = Camera3D::orthographic(Vector3::z(), Vector3::zeros(), Vector3::y(), 10.0);
+        camera.ortho_zoom(0.5, 2.0, 40.0);
+        assert_eq!(camera.fovy, 5.0);
+        camera.ortho_zoom(0.1, 2.0, 40.0);
+        assert_eq!(camera.fovy, 2.0);
+        camera.ortho_zoom(100.0, 2.0, 40.0);
+        assert_eq!(camera.fovy, 40.0);
+
+        let mut perspective =
+            Camera3D::perspective(Vector3::z(), Vector3::zeros(), Vector3::y(), 60.0);
+        perspective.ortho_zoom(0.5, 2.0, 40.0);
+        assert_eq!(perspective.fovy, 60.0);
+    }
+
+    #[test]
+    fn test_dolly_clamped() {
+        let start = Vector3::new(0.0, 3.0, 4.0);
+        let mut camera = Camera3D::perspective(start, Vector3::zeros(), Vector3::y(), 60.0);
+        camera.dolly_clamped(2.0, 1.0, 20.0);
+        assert!((camera.position - start * 0.6).norm() < 1e-5);
+
+        // Pushing past the target stops short of it, still looking the same way
+        camera.dolly_clamped(100.0, 1.0, 20.0);
+        assert!((camera.position.norm() - 1.0).abs() < 1e-5);
+        assert!((camera.position - start / 5.0).norm() < 1e-5);
+
+        camera.dolly_clamped(-100.0, 1.0, 20.0);
+        assert!((camera.position.norm() - 20.0).abs() < 1e-4);
+        assert_eq!(camera.target, Vector3::zeros());
+    }
+
+    #[test]
+    fn test_fov_override_restores() {
+        fn sprint(camera: &mut Camera3D<f32>, stop_early: bool) -> f32 {
+            let mut guard = camera.fov_override(80.0);
+            if stop_early {
+                return guard.fovy;
+            }
+            guard.fovy += 5.0;
+            guard.fovy
+        }
+
+        let mut camera = Camera3D::perspective(Vector3::zeros(), Vector3::z(), Vector3::y(), 60.0);
+        assert_eq!(sprint(&mut camera, true), 80.0);
+        assert_eq!(camera.fovy, 60.0);
+        assert_eq!(sprint(&mut camera, false), 85.0);
+        assert_eq!(camera.fovy, 60.0);
+
+        let guard = camera.fov_override(90.0);
+        assert_eq!(guard.previous(), 60.0);
+        drop(guard);
+        assert_eq!(camera.fovy, 60.0);
+    }
+
+    #[test]
+    #[cfg(all(feature = "integration-tests", feature = "raylib-4-5"))]
+    fn test_ffi_basis_matches() {
+        crate::core::test_harness::with_test_window(|rl, _| {
+            let camera = Camera3D::perspective(
+                Vector3::new(3.0f32, 1.0, -2.0),
+                Vector3::new(-1.0, 1.0, 4.0),
+                Vector3::y(),
+                60.0,
+            );
+            assert!((rl.get_camera_forward(&camera) - camera.forward()).norm() < 1e-5);
+            assert!((rl.get_camera_right(&camera) - camera.right()).norm() < 1e-5);
+            assert!((rl.get_camera_up(&camera) - camera.up_normalized()).norm() < 1e-5);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_controller_in_window() {
+        use crate::consts::KeyboardKey::KEY_W;
+        use crate::core::camera::input::FakeInput;
+        use crate::core::math::ToFFIVector;
+
+        crate::core::test_harness::with_test_window(|rl, thread| {
+            let mut camera = Camera3D::perspective(
+                Vector3::new(0.0f32, 1.0, 0.0),
+                Vector3::z(),
+                Vector3::y(),
+                60.0,
+            );
+            let mut controller = FirstPersonController::from_camera(&camera);
+            let input = FakeInput {
+                down: vec![KEY_W],
+                ..Default::default()
+            };
+            let screen = Vector2::new(rl.get_screen_width() as f32, rl.get_screen_height() as f32);
+            let lens = CameraLens::default();
+            for _ in 0..30 {
+                let _d = rl.begin_drawing(thread);
+                controller.update(&mut camera, &input, 1.0 / 60.0);
+            }
+            assert!(
+                camera.position.z > 2.4,
+                "walking forward should move the camera"
+            );
+
+            // Our projection has to agree with raylib's
+            let point = camera.position + Vector3::new(1.0, 0.5, 6.0);
+            let ours =
+                camera.world_to_screen(point, screen, screen.x / screen.y, lens.near, lens.far);
+            let theirs = unsafe {
+                ffi::GetWorldToScreen(vec3_to_f32(&point).to_ffi_vector(), (&camera).into())
+            };
+            assert!((ours - Vector2::new(theirs.x, theirs.y)).norm() < 0.5);
+        });
+    }
+}
@@ -0,0 +1,130 @@
+//! Splines for moving a camera along a fixed route
+use nalgebra::{Scalar, Vector3};
+use num_traits::Float;
+
+use crate::core::math::{vec3_from_f32, vec3_to_f32};
+
+/// A smooth path through a list of points, for cutscenes and on-rails sequences.
+///
+/// The curve is a Catmull-Rom spline, so it passes through every point. Positions along it are given as `t` from 0 at the first point to 1 at the last, with each segment between two points taking an equal share of `t`.
+#[derive(Debug, Clone, Default)]
+pub struct CameraPath<T>
+where
+    T: Scalar,
+{
+    points: Vec<Vector3<T>>,
+}
+
+impl<T> CameraPath<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a path through `points`.
+    pub fn new(points: Vec<Vector3<T>>) -> Self {
+        CameraPath { points }
+    }
+
+    /// Returns the points the path goes through.
+    pub fn points(&self) -> &[Vector3<T>] {
+        &self.points
+    }
+
+    /// Adds a point to the end of the path.
+    pub fn push(&mut self, point: Vector3<T>) {
+        self.points.push(point);
+    }
+
+    /// Returns the number of points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the path has no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the position at `t`, clamped to `[0, 1]`.
+    ///
+    /// A path with a single point stays there, and an empty path is at the origin.
+    pub fn sample(&self, t: f32) -> Vector3<T> {
+        match self.segment(t) {
+            Some(([p0, p1, p2, p3], u)) => {
+                let (u2, u3) = (u * u, u * u * u);
+                let p = (p1 * 2.0
+                    + (p2 - p0) * u
+                    + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * u2
+                    + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * u3)
+                    * 0.5;
+                vec3_from_f32(&p)
+            }
+            None => self
+                .points
+                .first()
+                .copied()
+                .unwrap_or_else(|| Vector3::new(T::zero(), T::zero(), T::zero())),
+        }
+    }
+
+    /// Returns the normalized direction of travel at `t`, clamped to `[0, 1]`.
+    ///
+    /// Returns zero when the path has fewer than two distinct points.
+    pub fn tangent(&self, t: f32) -> Vector3<T> {
+        let d = match self.segment(t) {
+            Some(([p0, p1, p2, p3], u)) => {
+                ((p2 - p0)
+                    + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * (2.0 * u)
+                    + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * (3.0 * u * u))
+                    * 0.5
+            }
+            None => Vector3::zeros(),
+        };
+        vec3_from_f32(&d.try_normalize(f32::EPSILON).unwrap_or_else(Vector3::zeros))
+    }
+
+    /// Finds the four control points around `t` and how far along their middle segment it is.
+    fn segment(&self, t: f32) -> Option<([Vector3<f32>; 4], f32)> {
+        let n = self.points.len();
+        if n < 2 {
+            return None;
+        }
+        let segments = n - 1;
+        let s = t.max(0.0).min(1.0) * segments as f32;
+        let i = (s.floor() as usize).min(segments - 1);
+        let u = s - i as f32;
+        // The ends are extended by repeating the first and last points
+        let at = |j: isize| vec3_to_f32(&self.points[j.max(0).min(n as isize - 1) as usize]);
+        let i = i as isize;
+        Some(([at(i - 1), at(i), at(i + 1), at(i + 2)], u))
+    }
+}
+
+#[cfg(test)]
+mod test_path {
+    use super::*;
+
+    fn path() -> CameraPath<f32> {
+        CameraPath::new(vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 10.0),
+        ])
+    }
+
+    #[test]
+    fn test_path_passes_through_points() {
+        let path = path();
+        assert!((path.sample(0.0) - path.points()[0]).norm() < 1e-5);
+        assert!((path.sample(0.5) - path.points()[1]).norm() < 1e-5);
+        assert!((path.sample(1.0) - path.points()[2]).norm() < 1e-5);
+        assert!((path.sample(2.0) - path.points()[2]).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_path_tangent() {
+        let path = path();
+        assert!(path.tangent(0.0).x > 0.9);
+        assert!(path.tangent(1.0).z > 0.9);
+        assert_eq!(CameraPath::<f32>::default().tangent(0.5), Vector3::zeros());
+    }
+}
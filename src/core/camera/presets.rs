@@ -0,0 +1,199 @@
+//! Named camera poses that can be switched to at runtime
+use num_traits::Float;
+
+use crate::consts::KeyboardKey;
+use crate::core::camera::{Camera3D, CameraInput};
+
+/// A list of named camera poses, for jumping between saved viewpoints while debugging.
+#[derive(Debug, Clone, Default)]
+pub struct CameraPresets<T>
+where
+    T: Float,
+{
+    presets: Vec<(String, Camera3D<T>)>,
+}
+
+impl<T> CameraPresets<T>
+where
+    T: Float,
+{
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        CameraPresets {
+            presets: Vec::new(),
+        }
+    }
+
+    /// Saves `camera` under `name`, replacing any preset already using that name.
+    pub fn insert(&mut self, name: &str, camera: Camera3D<T>) {
+        match self.presets.iter_mut().find(|(n, _)| n == name) {
+            Some(preset) => preset.1 = camera,
+            None => self.presets.push((name.to_string(), camera)),
+        }
+    }
+
+    /// Returns the preset called `name`.
+    pub fn get(&self, name: &str) -> Option<&Camera3D<T>> {
+        self.presets
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, camera)| camera)
+    }
+
+    /// Removes and returns the preset called `name`.
+    pub fn remove(&mut self, name: &str) -> Option<Camera3D<T>> {
+        let index = self.presets.iter().position(|(n, _)| n == name)?;
+        Some(self.presets.remove(index).1)
+    }
+
+    /// Returns the preset names in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|(n, _)| n.as_str())
+    }
+
+    /// Returns the number of presets.
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// Returns true if there are no presets.
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+}
+
+/// A one line text box for typing a preset name, see [`CameraPresets`].
+///
+/// Call [`PresetEntry::update`] every frame while the box is open. Typed characters are appended, backspace deletes and enter looks the name up.
+#[derive(Debug, Clone, Default)]
+pub struct PresetEntry {
+    text: String,
+}
+
+impl PresetEntry {
+    /// Creates an empty entry.
+    pub fn new() -> Self {
+        PresetEntry::default()
+    }
+
+    /// Returns what has been typed so far, for drawing the box.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Reads this frame's typing. When enter is pressed on the name of a preset, clears the entry and returns that preset.
+    ///
+    /// Enter on an unknown name keeps the text so it can be corrected.
+    pub fn update<T>(
+        &mut self,
+        input: &dyn CameraInput,
+        presets: &CameraPresets<T>,
+    ) -> Option<Camera3D<T>>
+    where
+        T: Float,
+    {
+        while let Some(c) = input.char_pressed() {
+            if !c.is_control() {
+                self.text.push(c);
+            }
+        }
+        if input.key_pressed(KeyboardKey::KEY_BACKSPACE) {
+            self.text.pop();
+        }
+        if input.key_pressed(KeyboardKey::KEY_ENTER) {
+            let camera = *presets.get(self.text.trim())?;
+            self.text.clear();
+            return Some(camera);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test_presets {
+    use super::*;
+    use crate::consts::KeyboardKey::*;
+    use crate::core::camera::input::FakeInput;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn test_chars_drain_in_order() {
+        let input = FakeInput::default();
+        input.chars.borrow_mut().extend("top".chars());
+        assert_eq!(input.char_pressed(), Some('t'));
+        assert_eq!(input.char_pressed(), Some('o'));
+        assert_eq!(input.char_pressed(), Some('p'));
+        assert_eq!(input.char_pressed(), None);
+    }
+
+    #[test]
+    fn test_entry_activates_preset() {
+        let mut presets = CameraPresets::new();
+        let top = Camera3D::perspective(
+            Vector3::new(0.0f32, 20.0, 0.1),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        presets.insert("top", top);
+
+        let mut entry = PresetEntry::new();
+        let mut input = FakeInput::default();
+        input.chars.borrow_mut().extend("tpx".chars());
+        assert!(entry.update(&input, &presets).is_none());
+        assert_eq!(entry.text(), "tpx");
+
+        input.pressed = vec![KEY_BACKSPACE];
+        entry.update(&input, &presets);
+        input.pressed = vec![KEY_BACKSPACE];
+        entry.update(&input, &presets);
+        input.pressed.clear();
+        input.chars.borrow_mut().extend("op".chars());
+        entry.update(&input, &presets);
+        assert_eq!(entry.text(), "top");
+
+        input.pressed = vec![KEY_ENTER];
+        let camera = entry.update(&input, &presets).unwrap();
+        assert_eq!(camera.position, top.position);
+        assert_eq!(entry.text(), "");
+        assert!(input.chars.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_entry_replays_typing() {
+        use crate::core::camera::InputScript;
+
+        let mut presets = CameraPresets::new();
+        let side = Camera3D::perspective(
+            Vector3::new(20.0f32, 1.0, 0.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        presets.insert("side", side);
+
+        let mut script = InputScript::new(&[KEY_ENTER, KEY_BACKSPACE]);
+        let mut input = FakeInput::default();
+        input.chars.borrow_mut().extend("sid".chars());
+        script.record(&input, 0.016);
+        input.chars.borrow_mut().push_back('e');
+        script.record(&input, 0.016);
+        input.pressed = vec![KEY_ENTER];
+        script.record(&input, 0.016);
+        assert_eq!(script.frames()[0].chars, vec!['s', 'i', 'd']);
+
+        for _ in 0..2 {
+            let mut entry = PresetEntry::new();
+            let results: Vec<_> = script
+                .frames()
+                .iter()
+                .map(|frame| {
+                    frame.rewind_chars();
+                    entry.update(frame, &presets)
+                })
+                .collect();
+            assert!(results[0].is_none() && results[1].is_none());
+            assert_eq!(results[2].unwrap().position, side.position);
+        }
+    }
+}
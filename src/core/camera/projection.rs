@@ -0,0 +1,1062 @@
+//! View and projection math for [`Camera3D`]
+use nalgebra::{Matrix4, Point3, Scalar, Vector2, Vector3, Vector4};
+use num_traits::Float;
+use parry3d::query::Ray;
+
+use crate::core::camera::Camera3D;
+use crate::core::collision::{get_collision_ray_ground, BoundingBox};
+use crate::core::math::{
+    deg_to_rad, ndc_to_screen, rad_to_deg, screen_to_ndc, vec3_from_f32, vec3_to_f32, Viewport,
+};
+use crate::ffi;
+
+/// Pairs of indices into [`Camera3D::frustum_corners`] making up the 12 edges of a frustum.
+pub const FRUSTUM_EDGES: [(usize, usize); 12] = [
+    // Near plane
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    // Far plane
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    // Near to far
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Clip planes kept alongside a [`Camera3D`], so they don't have to be passed to every projection call.
+///
+/// [`Camera3D`] mirrors raylib's layout and can't hold these itself. The defaults are the clip planes raylib uses in `BeginMode3D`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CameraLens {
+    pub near: f32,
+    pub far: f32,
+}
+
+impl CameraLens {
+    /// Creates a lens with the given clip planes.
+    pub fn new(near: f32, far: f32) -> Self {
+        CameraLens { near, far }
+    }
+}
+
+impl Default for CameraLens {
+    fn default() -> Self {
+        CameraLens {
+            near: 0.01,
+            far: 1000.0,
+        }
+    }
+}
+
+/// A camera's combined `projection * view` matrix and its inverse, for projecting many points with the same camera.
+///
+/// Build one per frame with [`ViewProjection::from_camera`] and reuse it, rather than letting every call to [`Camera3D::world_to_screen`] rebuild both matrices.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ViewProjection {
+    view: Matrix4<f32>,
+    matrix: Matrix4<f32>,
+    // Left out by the one-off Camera3D helpers, which only ever project forward
+    inverse: Option<Matrix4<f32>>,
+}
+
+impl ViewProjection {
+    /// Combines the camera's view and projection matrices, see [`Camera3D::get_projection_matrix`].
+    pub fn from_camera<T>(camera: &Camera3D<T>, aspect: f32, near: f32, far: f32) -> Self
+    where
+        T: Float + Scalar,
+    {
+        Self::from_matrices(
+            camera.get_view_matrix(),
+            camera.get_projection_matrix(aspect, near, far),
+        )
+    }
+
+    /// Combines an existing view and projection matrix.
+    ///
+    /// If the result can't be inverted, say because the clip planes are equal, [`ViewProjection::unproject`] returns NaN.
+    pub fn from_matrices(view: Matrix4<f32>, projection: Matrix4<f32>) -> Self {
+        let mut view_projection = Self::forward_only(view, projection);
+        view_projection.inverse = Some(invert(&view_projection.matrix));
+        view_projection
+    }
+
+    /// Like [`ViewProjection::from_camera`] but skips inverting the matrix, for a single projection that never unprojects.
+    pub(crate) fn from_camera_forward_only<T>(
+        camera: &Camera3D<T>,
+        aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> Self
+    where
+        T: Float + Scalar,
+    {
+        Self::forward_only(
+            camera.get_view_matrix(),
+            camera.get_projection_matrix(aspect, near, far),
+        )
+    }
+
+    fn forward_only(view: Matrix4<f32>, projection: Matrix4<f32>) -> Self {
+        ViewProjection {
+            view,
+            matrix: projection * view,
+            inverse: None,
+        }
+    }
+
+    /// Returns the view matrix on its own.
+    pub fn view(&self) -> &Matrix4<f32> {
+        &self.view
+    }
+
+    /// Returns the combined `projection * view` matrix.
+    pub fn matrix(&self) -> &Matrix4<f32> {
+        &self.matrix
+    }
+
+    /// Returns the inverse of [`ViewProjection::matrix`].
+    pub fn inverse(&self) -> Matrix4<f32> {
+        self.inverse.unwrap_or_else(|| invert(&self.matrix))
+    }
+
+    /// Projects a world space point to normalized device coordinates, each in `[-1, 1]` when the point is in view.
+    ///
+    /// Like [`Camera3D::world_to_screen`], points behind the camera come out mirrored.
+    pub fn project(&self, point: &Vector3<f32>) -> Vector3<f32> {
+        divide_w(self.matrix * Vector4::new(point.x, point.y, point.z, 1.0))
+    }
+
+    /// Turns normalized device coordinates back into a world space point, the inverse of [`ViewProjection::project`].
+    ///
+    /// An NDC `z` of -1 is on the near plane and 1 on the far plane.
+    pub fn unproject(&self, ndc: &Vector3<f32>) -> Vector3<f32> {
+        let world = self.inverse() * Vector4::new(ndc.x, ndc.y, ndc.z, 1.0);
+        world.xyz() / world.w
+    }
+
+    /// Projects a world space point to screen coordinates, see [`Camera3D::world_to_screen`].
+    pub fn world_to_screen(&self, point: &Vector3<f32>, screen_size: Vector2<f32>) -> Vector2<f32> {
+        ndc_to_screen(self.project(point).xy(), Viewport::from_size(screen_size))
+    }
+
+    /// Projects a world space point to screen coordinates along with its depth, see [`Camera3D::world_to_screen_with_depth`].
+    pub fn world_to_screen_with_depth(
+        &self,
+        point: &Vector3<f32>,
+        screen_size: Vector2<f32>,
+    ) -> (Vector2<f32>, f32) {
+        let ndc = self.project(point);
+        let screen = ndc_to_screen(ndc.xy(), Viewport::from_size(screen_size));
+        // The view looks down -Z, anything with z >= 0 is level with or behind the camera
+        let view_z = (self.view * Vector4::new(point.x, point.y, point.z, 1.0)).z;
+        let depth = if view_z >= 0.0 {
+            f32::INFINITY
+        } else {
+            (ndc.z + 1.0) / 2.0
+        };
+        (screen, depth)
+    }
+
+    /// Returns how fast a point moves across the screen in pixels per second, see [`Camera3D::screen_velocity`].
+    pub fn screen_velocity(
+        &self,
+        world_pos: &Vector3<f32>,
+        world_velocity: &Vector3<f32>,
+        dt: f32,
+        screen_size: Vector2<f32>,
+    ) -> Option<Vector2<f32>> {
+        if dt <= 0.0 {
+            return None;
+        }
+        let project = |p: &Vector3<f32>| {
+            let (screen, depth) = self.world_to_screen_with_depth(p, screen_size);
+            if depth.is_infinite() {
+                None
+            } else {
+                Some(screen)
+            }
+        };
+        let now = project(world_pos)?;
+        let then = project(&(world_pos + world_velocity * dt))?;
+        Some((then - now) / dt)
+    }
+
+    /// Returns true if `world_point` shows up within `radius_pixels` of `cursor`, see [`Camera3D::is_near_cursor`].
+    pub fn is_near_cursor(
+        &self,
+        world_point: &Vector3<f32>,
+        cursor: Vector2<f32>,
+        radius_pixels: f32,
+        screen_size: Vector2<f32>,
+    ) -> bool {
+        let (screen, depth) = self.world_to_screen_with_depth(world_point, screen_size);
+        (0.0..=1.0).contains(&depth) && (screen - cursor).norm() <= radius_pixels
+    }
+
+    /// Returns the screen rectangle enclosing `bbox`, see [`Camera3D::screen_bounds_of_box`].
+    pub fn screen_bounds_of_box<T>(
+        &self,
+        bbox: &BoundingBox<T>,
+        screen_size: Vector2<f32>,
+    ) -> Option<(Vector2<f32>, Vector2<f32>)>
+    where
+        T: Float + Scalar,
+    {
+        let (min, max) = (vec3_to_f32(&bbox.min), vec3_to_f32(&bbox.max));
+        // The bottom face then the top face, each going around the same way, so the corners line up with FRUSTUM_EDGES
+        let mut corners = [Vector4::zeros(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let x = if i % 4 == 1 || i % 4 == 2 {
+                max.x
+            } else {
+                min.x
+            };
+            let y = if i >= 4 { max.y } else { min.y };
+            let z = if i % 4 >= 2 { max.z } else { min.z };
+            *corner = self.matrix * Vector4::new(x, y, z, 1.0);
+        }
+
+        // Clip space is still linear, and the near plane there is z = -w for both kinds of projection
+        let near_side = |c: &Vector4<f32>| c.z + c.w;
+        let mut points: Vec<Vector4<f32>> = corners
+            .iter()
+            .copied()
+            .filter(|c| near_side(c) >= 0.0)
+            .collect();
+        for &(a, b) in FRUSTUM_EDGES.iter() {
+            let (a, b) = (corners[a], corners[b]);
+            let (da, db) = (near_side(&a), near_side(&b));
+            if (da >= 0.0) != (db >= 0.0) {
+                points.push(a + (b - a) * (da / (da - db)));
+            }
+        }
+        if points.is_empty() {
+            return None;
+        }
+
+        let viewport = Viewport::from_size(screen_size);
+        let mut lo = Vector2::repeat(f32::INFINITY);
+        let mut hi = Vector2::repeat(f32::NEG_INFINITY);
+        for clip in points {
+            let screen = ndc_to_screen(divide_w(clip).xy(), viewport);
+            lo = lo.inf(&screen);
+            hi = hi.sup(&screen);
+        }
+        Some((lo, hi))
+    }
+}
+
+// A matrix that can't be inverted turns into zeros, so unprojecting with it gives NaN
+fn invert(matrix: &Matrix4<f32>) -> Matrix4<f32> {
+    matrix.try_inverse().unwrap_or_else(Matrix4::zeros)
+}
+
+/// Divides clip coordinates by `w`, nudging a `w` of zero so nothing divides by it.
+fn divide_w(clip: Vector4<f32>) -> Vector3<f32> {
+    let w = if clip.w.abs() > f32::EPSILON {
+        clip.w
+    } else {
+        f32::EPSILON
+    };
+    clip.xyz() / w
+}
+
+impl<T> Camera3D<T>
+where
+    T: Float + Scalar,
+{
+    /// Returns the camera's view matrix, the same one raylib builds in `BeginMode3D`.
+    pub fn get_view_matrix(&self) -> Matrix4<f32> {
+        Matrix4::look_at_rh(
+            &Point3::from(vec3_to_f32(&self.position)),
+            &Point3::from(vec3_to_f32(&self.target)),
+            &vec3_to_f32(&self.up),
+        )
+    }
+
+    /// Returns the camera's projection matrix, the same one raylib builds in `BeginMode3D`.
+    ///
+    /// `fovy` fixes the vertical field of view, so a wider window sees more to the sides and the same amount vertically (Hor+ scaling).
+    /// For orthographic cameras `fovy` is the height of the view volume in world units.
+    /// To match what raylib draws, take `aspect` from the framebuffer's render size rather than the screen size, they differ on high-DPI displays.
+    pub fn get_projection_matrix(&self, aspect: f32, near: f32, far: f32) -> Matrix4<f32> {
+        self.projection_for_fovy(self.fovy, aspect, near, far)
+    }
+
+    /// Returns the camera's projection matrix using the clip planes from `lens`.
+    pub fn get_projection_matrix_with_lens(&self, aspect: f32, lens: &CameraLens) -> Matrix4<f32> {
+        self.get_projection_matrix(aspect, lens.near, lens.far)
+    }
+
+    /// Returns the camera's projection matrix using the default [`CameraLens`] clip planes.
+    pub fn get_projection_matrix_default(&self, aspect: f32) -> Matrix4<f32> {
+        self.get_projection_matrix_with_lens(aspect, &CameraLens::default())
+    }
+
+    /// Returns a projection matrix that keeps the horizontal field of view `fovy` has at `base_aspect` (Vert- scaling).
+    ///
+    /// Windows wider than `base_aspect` see the same amount to the sides and less vertically, narrower ones see more vertically.
+    /// At `aspect == base_aspect` this is the same as [`Camera3D::get_projection_matrix`].
+    pub fn get_projection_matrix_vert_minus(
+        &self,
+        aspect: f32,
+        base_aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> Matrix4<f32> {
+        let fovy = match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => self.fovy * base_aspect / aspect,
+            _ => {
+                let half = (deg_to_rad(self.fovy) / 2.0).tan() * base_aspect / aspect;
+                rad_to_deg(2.0 * half.atan())
+            }
+        };
+        self.projection_for_fovy(fovy, aspect, near, far)
+    }
+
+    /// Returns the projection for a cutscene drawn between letterbox bars, covering `letterbox_fraction` of the screen height in total.
+    ///
+    /// The picture inside the bars keeps the horizontal field of view of the full `full_aspect` screen and is cropped vertically, so the horizontal composition doesn't change when the bars slide in.
+    /// Use it with a viewport or scissor rectangle the size of the area between the bars. A fraction of 0 is the same as [`Camera3D::get_projection_matrix`].
+    pub fn get_projection_matrix_letterboxed(
+        &self,
+        full_aspect: f32,
+        letterbox_fraction: f32,
+        near: f32,
+        far: f32,
+    ) -> Matrix4<f32> {
+        let visible = 1.0 - letterbox_fraction.max(0.0).min(0.99);
+        self.get_projection_matrix_vert_minus(full_aspect / visible, full_aspect, near, far)
+    }
+
+    /// Blends between a perspective (`t = 0`) and an orthographic (`t = 1`) projection, for dolly-zoom style transitions.
+    ///
+    /// The orthographic view is sized so that things at the distance of `target` stay the same size, which keeps the subject steady while the rest of the scene flattens out.
+    /// This works the same whichever type the camera is, for orthographic cameras `fovy` is the height at the target distance. `t` is clamped to `[0, 1]`.
+    pub fn blend_projection_matrix(
+        &self,
+        t: f32,
+        aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> Matrix4<f32> {
+        let distance = (vec3_to_f32(&self.target) - vec3_to_f32(&self.position))
+            .norm()
+            .max(f32::EPSILON);
+        let (fovy, height) = match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => (
+                rad_to_deg(2.0 * (self.fovy / 2.0 / distance).atan()),
+                self.fovy,
+            ),
+            _ => (
+                self.fovy,
+                2.0 * distance * (deg_to_rad(self.fovy) / 2.0).tan(),
+            ),
+        };
+        let top = height / 2.0;
+        let right = top * aspect;
+        let perspective = Matrix4::new_perspective(aspect, deg_to_rad(fovy), near, far);
+        let orthographic = Matrix4::new_orthographic(-right, right, -top, top, near, far);
+
+        let t = t.max(0.0).min(1.0);
+        perspective * (1.0 - t) + orthographic * t
+    }
+
+    /// Projects a world space point to screen coordinates, with `(0, 0)` at the top left of the screen.
+    ///
+    /// Points behind the camera project to a mirrored position, use [`Camera3D::world_to_screen_with_depth`] to tell them apart.
+    /// The result is in the units of `screen_size`: pass the screen size to place UI and compare with the mouse, or the render size to address framebuffer pixels on high-DPI displays.
+    /// Each call builds the camera's matrices from scratch. To project many points, build a [`ViewProjection`] once and use its methods instead.
+    pub fn world_to_screen(
+        &self,
+        point: Vector3<T>,
+        screen_size: Vector2<f32>,
+        aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> Vector2<f32> {
+        self.world_to_screen_with_depth(point, screen_size, aspect, near, far)
+            .0
+    }
+
+    /// Projects a world space point to screen coordinates along with its depth.
+    ///
+    /// Depth is 0 at the near plane and 1 at the far plane, the same value that ends up in the depth buffer.
+    /// Points at or behind the camera always have a depth greater than 1 (infinity), so checking `depth <= 1.0` also rejects them.
+    pub fn world_to_screen_with_depth(
+        &self,
+        point: Vector3<T>,
+        screen_size: Vector2<f32>,
+        aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> (Vector2<f32>, f32) {
+        ViewProjection::from_camera_forward_only(self, aspect, near, far)
+            .world_to_screen_with_depth(&vec3_to_f32(&point), screen_size)
+    }
+
+    /// Slides the camera in its view plane so that whatever is grabbed at the depth of `target` follows a drag of `pixel_delta` pixels.
+    ///
+    /// One pixel covers the view height at the target's distance divided by `screen_size.y` (or `fovy` divided by it for orthographic cameras), so the grabbed point stays under the cursor. `position` and `target` move together, the direction is kept.
+    pub fn pan_screen_space(&mut self, pixel_delta: Vector2<f32>, screen_size: Vector2<f32>) {
+        let (_, right, up) = self.basis();
+        let position = vec3_to_f32(&self.position);
+        let target = vec3_to_f32(&self.target);
+        let view_height = match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => self.fovy,
+            _ => 2.0 * (target - position).norm() * (deg_to_rad(self.fovy) / 2.0).tan(),
+        };
+        let per_pixel = view_height / screen_size.y.max(1.0);
+        // Screen Y points down, so dragging down moves the camera up
+        let shift = (up * pixel_delta.y - right * pixel_delta.x) * per_pixel;
+        self.position = vec3_from_f32(&(position + shift));
+        self.target = vec3_from_f32(&(target + shift));
+    }
+
+    /// Returns how fast a point moving at `world_velocity` moves across the screen, in pixels per second, for motion blur or leading a reticle.
+    ///
+    /// Compares where the point projects now with where it projects `dt` seconds later.
+    /// Returns `None` if the point is behind the camera at either end, or if `dt` isn't positive.
+    pub fn screen_velocity(
+        &self,
+        world_pos: Vector3<T>,
+        world_velocity: Vector3<T>,
+        dt: f32,
+        screen_size: Vector2<f32>,
+        aspect: f32,
+        lens: &CameraLens,
+    ) -> Option<Vector2<f32>> {
+        ViewProjection::from_camera_forward_only(self, aspect, lens.near, lens.far).screen_velocity(
+            &vec3_to_f32(&world_pos),
+            &vec3_to_f32(&world_velocity),
+            dt,
+            screen_size,
+        )
+    }
+
+    /// Returns true if `world_point` shows up within `radius_pixels` of `cursor`, for hover highlighting and soft selection.
+    ///
+    /// `cursor` is in the same units as `screen_size`. Points outside the clip planes of `lens`, including anything behind the camera, are never near.
+    pub fn is_near_cursor(
+        &self,
+        world_point: Vector3<T>,
+        cursor: Vector2<f32>,
+        radius_pixels: f32,
+        screen_size: Vector2<f32>,
+        aspect: f32,
+        lens: &CameraLens,
+    ) -> bool {
+        ViewProjection::from_camera_forward_only(self, aspect, lens.near, lens.far).is_near_cursor(
+            &vec3_to_f32(&world_point),
+            cursor,
+            radius_pixels,
+            screen_size,
+        )
+    }
+
+    /// Turns a depth buffer value back into the distance in front of the camera, for post-processing effects.
+    ///
+    /// `depth` is in the same 0 to 1 range as [`Camera3D::world_to_screen_with_depth`] returns, using the same `near` and `far` the scene was drawn with.
+    /// Perspective depth is bunched up toward the near plane and has to be undone. Orthographic depth is already linear and only gets scaled to the clip range.
+    pub fn linearize_depth(&self, depth: f32, near: f32, far: f32) -> f32 {
+        match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => near + depth * (far - near),
+            _ => {
+                let ndc = depth * 2.0 - 1.0;
+                2.0 * near * far / (far + near - ndc * (far - near))
+            }
+        }
+    }
+
+    /// Returns the ray through a screen position, like [`RaylibHandle::get_mouse_ray`](crate::core::RaylibHandle::get_mouse_ray) but without needing a window.
+    ///
+    /// `screen_pos` is in the same units as `screen_size`, so mouse positions go with the screen size rather than the render size. The aspect ratio comes from `screen_size`. Perspective rays start at `position`, orthographic rays start on the camera plane and all point straight ahead. The direction is normalized.
+    pub fn screen_to_ray(&self, screen_pos: Vector2<f32>, screen_size: Vector2<f32>) -> Ray {
+        let (forward, right, up) = self.basis();
+        let ndc = screen_to_ndc(screen_pos, Viewport::from_size(screen_size));
+        let aspect = screen_size.x / screen_size.y.max(1.0);
+        let position = vec3_to_f32(&self.position);
+        match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => {
+                let half = self.fovy / 2.0;
+                let origin = position + right * ndc.x * half * aspect + up * ndc.y * half;
+                Ray::new(Point3::from(origin), forward)
+            }
+            _ => {
+                let half = (deg_to_rad(self.fovy) / 2.0).tan();
+                let dir = forward + right * ndc.x * half * aspect + up * ndc.y * half;
+                Ray::new(Point3::from(position), dir.normalize())
+            }
+        }
+    }
+
+    /// Returns where the four corners of the screen land on the horizontal plane at height `plane_y`, for ground decals and fog of war.
+    ///
+    /// The corners are ordered top left, top right, bottom right, bottom left as seen on screen. Returns `None` if any corner looks above the horizon and never reaches the plane.
+    pub fn screen_corners_on_plane(
+        &self,
+        screen_size: Vector2<f32>,
+        plane_y: T,
+    ) -> Option<[Vector3<T>; 4]> {
+        let corners = [
+            Vector2::zeros(),
+            Vector2::new(screen_size.x, 0.0),
+            screen_size,
+            Vector2::new(0.0, screen_size.y),
+        ];
+        let plane_y = plane_y.to_f32().unwrap();
+        let mut hits = [Vector3::new(T::zero(), T::zero(), T::zero()); 4];
+        for (hit, corner) in hits.iter_mut().zip(corners.iter()) {
+            let point =
+                get_collision_ray_ground(&self.screen_to_ray(*corner, screen_size), plane_y)?;
+            *hit = vec3_from_f32(&point);
+        }
+        Some(hits)
+    }
+
+    /// Returns the corners `(min, max)` of the screen rectangle enclosing `bbox`, for drawing selection boxes around objects.
+    ///
+    /// The parts of the box behind the near plane are cut off before projecting, so a box the camera is partly inside still gets a sensible rectangle instead of one flipped by points behind the camera.
+    /// The rectangle isn't clamped to the screen. Returns `None` if the whole box is behind the near plane.
+    pub fn screen_bounds_of_box(
+        &self,
+        bbox: &BoundingBox<T>,
+        screen_size: Vector2<f32>,
+        aspect: f32,
+        near: f32,
+        far: f32,
+    ) -> Option<(Vector2<f32>, Vector2<f32>)> {
+        ViewProjection::from_camera_forward_only(self, aspect, near, far)
+            .screen_bounds_of_box(bbox, screen_size)
+    }
+
+    /// Moves the camera so every point in `targets` is in view, looking along `desired_direction` at their centroid. For spectator cameras following several players.
+    ///
+    /// `padding` is kept clear around each target in world units, treat it as the players' radius. Perspective cameras back away until everything fits, orthographic cameras change `fovy` instead.
+    /// `up` is kept, does nothing if `targets` is empty.
+    pub fn frame_targets(
+        &mut self,
+        targets: &[Vector3<T>],
+        desired_direction: Vector3<T>,
+        aspect: f32,
+        padding: f32,
+    ) {
+        if targets.is_empty() {
+            return;
+        }
+        let points: Vec<Vector3<f32>> = targets.iter().map(vec3_to_f32).collect();
+        let centroid = points.iter().sum::<Vector3<f32>>() / points.len() as f32;
+
+        let mut framed = *self;
+        framed.target = vec3_from_f32(&centroid);
+        framed.position = vec3_from_f32(&(centroid - vec3_to_f32(&desired_direction)));
+        let (forward, right, up) = framed.basis();
+
+        let tan_v = (deg_to_rad(self.fovy) / 2.0).tan();
+        let tan_h = tan_v * aspect;
+        let (mut distance, mut half_height) = (f32::EPSILON, f32::EPSILON);
+        for p in &points {
+            let offset = p - centroid;
+            let (x, y, z) = (offset.dot(&right), offset.dot(&up), offset.dot(&forward));
+            let (x, y) = (x.abs() + padding, y.abs() + padding);
+            distance = distance
+                .max(x / tan_h - z)
+                .max(y / tan_v - z)
+                .max(padding - z);
+            half_height = half_height.max(y).max(x / aspect);
+        }
+
+        if let ffi::CameraType::CAMERA_ORTHOGRAPHIC = self.type_ {
+            self.fovy = 2.0 * half_height;
+            // Far enough back that nothing is behind the camera
+            let behind = points
+                .iter()
+                .map(|p| (centroid - p).dot(&forward))
+                .fold(0.0f32, f32::max);
+            distance = behind + padding.max(1.0);
+        }
+        self.target = framed.target;
+        self.position = vec3_from_f32(&(centroid - forward * distance));
+    }
+
+    /// Returns the full angle in radians a sphere covers as seen from the camera.
+    ///
+    /// If the camera is inside the sphere this is π.
+    pub fn angular_size(&self, center: Vector3<T>, radius: T) -> f32 {
+        let distance = (vec3_to_f32(&center) - vec3_to_f32(&self.position)).norm();
+        let radius = radius.to_f32().unwrap().abs();
+        if distance <= radius {
+            std::f32::consts::PI
+        } else {
+            2.0 * (radius / distance).asin()
+        }
+    }
+
+    /// Returns roughly how many pixels the radius of a sphere covers on a screen `screen_height` pixels tall, for sizing reticles and labels.
+    ///
+    /// This is exact for a sphere in the middle of the view and slightly too small toward the edges. If the camera is inside the sphere this is `screen_height`, big enough to cover the screen vertically.
+    pub fn screen_radius(&self, center: Vector3<T>, radius: T, screen_height: f32) -> f32 {
+        if let ffi::CameraType::CAMERA_ORTHOGRAPHIC = self.type_ {
+            return radius.to_f32().unwrap().abs() / self.fovy * screen_height;
+        }
+        let angle = self.angular_size(center, radius);
+        if angle >= std::f32::consts::PI {
+            return screen_height;
+        }
+        let pixels =
+            (angle / 2.0).tan() / (deg_to_rad(self.fovy) / 2.0).tan() * screen_height / 2.0;
+        pixels.min(screen_height)
+    }
+
+    /// Returns the 8 world space corners of the view frustum.
+    ///
+    /// The near plane corners come first, then the far plane, each ordered bottom left, bottom right, top right, top left as seen from the camera.
+    /// [`FRUSTUM_EDGES`] lists which corners are connected.
+    pub fn frustum_corners(&self, aspect: f32, near: f32, far: f32) -> [Vector3<f32>; 8] {
+        let (forward, right, up) = self.basis();
+        let position = vec3_to_f32(&self.position);
+        let half_height = |distance: f32| match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => self.fovy / 2.0,
+            _ => (deg_to_rad(self.fovy) / 2.0).tan() * distance,
+        };
+
+        let mut corners = [Vector3::zeros(); 8];
+        for (i, distance) in [near, far].iter().enumerate() {
+            let center = position + forward * *distance;
+            let up = up * half_height(*distance);
+            let right = right * half_height(*distance) * aspect;
+            corners[i * 4] = center - right - up;
+            corners[i * 4 + 1] = center + right - up;
+            corners[i * 4 + 2] = center + right + up;
+            corners[i * 4 + 3] = center - right + up;
+        }
+        corners
+    }
+
+    fn projection_for_fovy(&self, fovy: f32, aspect: f32, near: f32, far: f32) -> Matrix4<f32> {
+        match self.type_ {
+            ffi::CameraType::CAMERA_ORTHOGRAPHIC => {
+                let top = fovy / 2.0;
+                let right = top * aspect;
+                Matrix4::new_orthographic(-right, right, -top, top, near, far)
+            }
+            _ => Matrix4::new_perspective(aspect, deg_to_rad(fovy), near, far),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_projection {
+    use super::*;
+    use nalgebra::Vector3;
+
+    const WIDE: f32 = 16.0 / 9.0;
+    const ULTRAWIDE: f32 = 21.0 / 9.0;
+
+    fn camera() -> Camera3D<f32> {
+        Camera3D::perspective(Vector3::z(), Vector3::zeros(), Vector3::y(), 60.0)
+    }
+
+    #[test]
+    fn test_hor_plus_keeps_vertical_fov() {
+        let c = camera();
+        let base = c.get_projection_matrix(WIDE, 0.1, 100.0);
+        let ultra = c.get_projection_matrix(ULTRAWIDE, 0.1, 100.0);
+        assert!((ultra[(1, 1)] - base[(1, 1)]).abs() < 1e-6);
+        assert!(
+            ultra[(0, 0)] < base[(0, 0)],
+            "ultrawide should see more horizontally"
+        );
+    }
+
+    #[test]
+    fn test_vert_minus_keeps_horizontal_fov() {
+        let c = camera();
+        let base = c.get_projection_matrix(WIDE, 0.1, 100.0);
+        let ultra = c.get_projection_matrix_vert_minus(ULTRAWIDE, WIDE, 0.1, 100.0);
+        assert!((ultra[(0, 0)] - base[(0, 0)]).abs() < 1e-5);
+        assert!(
+            ultra[(1, 1)] > base[(1, 1)],
+            "ultrawide should see less vertically"
+        );
+
+        let same = c.get_projection_matrix_vert_minus(WIDE, WIDE, 0.1, 100.0);
+        assert!((same - base).iter().all(|x| x.abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_world_to_screen_depth() {
+        let c = camera();
+        let screen = Vector2::new(1280.0, 720.0);
+        // The camera sits at z = 1 looking down -Z
+        let (pos, depth) =
+            c.world_to_screen_with_depth(Vector3::new(0.0, 0.0, 0.9), screen, WIDE, 0.1, 100.0);
+        assert!((pos - Vector2::new(640.0, 360.0)).norm() < 1e-2);
+        assert!(depth.abs() < 1e-4);
+
+        let (_, depth) =
+            c.world_to_screen_with_depth(Vector3::new(0.0, 0.0, -99.0), screen, WIDE, 0.1, 100.0);
+        assert!((depth - 1.0).abs() < 1e-4);
+
+        let (_, depth) =
+            c.world_to_screen_with_depth(Vector3::new(0.0, 0.0, 5.0), screen, WIDE, 0.1, 100.0);
+        assert!(depth > 1.0);
+
+        let up_right = c.world_to_screen(Vector3::new(1.0, 1.0, -1.0), screen, WIDE, 0.1, 100.0);
+        assert!(up_right.x > 640.0 && up_right.y < 360.0);
+    }
+
+    #[test]
+    fn test_pan_keeps_point_under_cursor() {
+        let screen = Vector2::new(1280.0, 720.0);
+        let aspect = screen.x / screen.y;
+        let mut camera = Camera3D::perspective(
+            Vector3::new(2.0f32, 3.0, 6.0),
+            Vector3::new(0.0, 0.5, 0.0),
+            Vector3::y(),
+            50.0,
+        );
+        let grabbed = camera.target;
+        let before = camera.world_to_screen(grabbed, screen, aspect, 0.01, 1000.0);
+        let delta = Vector2::new(120.0, -45.0);
+        camera.pan_screen_space(delta, screen);
+        let after = camera.world_to_screen(grabbed, screen, aspect, 0.01, 1000.0);
+        assert!(
+            (after - (before + delta)).norm() < 0.05,
+            "{:?}",
+            after - before
+        );
+    }
+
+    #[test]
+    fn test_screen_velocity_falls_off_with_distance() {
+        let camera = camera();
+        let screen = Vector2::new(1280.0, 720.0);
+        let sideways = Vector3::new(1.0, 0.0, 0.0);
+        let speed = |z: f32| {
+            camera
+                .screen_velocity(
+                    Vector3::new(0.0, 0.0, z),
+                    sideways,
+                    0.1,
+                    screen,
+                    WIDE,
+                    &CameraLens::default(),
+                )
+                .unwrap()
+        };
+        let near = speed(0.0);
+        let far = speed(-20.0);
+        assert!(near.y.abs() < 1e-3 && near.x > 0.0);
+        assert!(near.x > far.x * 10.0);
+
+        // Passing behind the camera within the step
+        let backwards = camera.screen_velocity(
+            Vector3::new(0.0, 0.0, 0.5),
+            Vector3::new(0.0, 0.0, 10.0),
+            0.1,
+            screen,
+            WIDE,
+            &CameraLens::default(),
+        );
+        assert!(backwards.is_none());
+    }
+
+    #[test]
+    fn test_linearize_depth() {
+        let (near, far) = (0.1, 100.0);
+        let camera = camera();
+        assert!((camera.linearize_depth(0.0, near, far) - near).abs() < 1e-4);
+        assert!((camera.linearize_depth(1.0, near, far) - far).abs() < 1e-2);
+
+        // A point 8 units in front of the camera
+        let (_, depth) = camera.world_to_screen_with_depth(
+            Vector3::new(0.0, 0.0, -7.0),
+            Vector2::new(100.0, 100.0),
+            1.0,
+            near,
+            far,
+        );
+        assert!((camera.linearize_depth(depth, near, far) - 8.0).abs() < 1e-2);
+
+        let ortho = Camera3D::orthographic(Vector3::z(), Vector3::zeros(), Vector3::y(), 10.0);
+        assert_eq!(ortho.linearize_depth(0.5, 0.0, 100.0), 50.0);
+    }
+
+    #[test]
+    fn test_view_projection_round_trip() {
+        let camera = Camera3D::perspective(
+            Vector3::new(3.0f32, 2.0, 6.0),
+            Vector3::new(0.0, 0.5, 0.0),
+            Vector3::y(),
+            60.0,
+        );
+        let screen = Vector2::new(1280.0, 720.0);
+        let vp = ViewProjection::from_camera(&camera, WIDE, 0.1, 100.0);
+        let points = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, -0.5, 0.5),
+            Vector3::new(-2.0, 1.0, -3.0),
+            Vector3::new(0.3, 2.5, 1.0),
+        ];
+        for p in points.iter() {
+            let ndc = vp.project(p);
+            assert!((vp.unproject(&ndc) - p).norm() < 1e-3);
+            // The cached matrix gives the same answer as projecting each point from scratch
+            let expected = camera.world_to_screen(*p, screen, WIDE, 0.1, 100.0);
+            assert!((vp.world_to_screen(p, screen) - expected).norm() < 1e-3);
+            let (_, depth) = camera.world_to_screen_with_depth(*p, screen, WIDE, 0.1, 100.0);
+            assert_eq!(vp.world_to_screen_with_depth(p, screen).1, depth);
+        }
+        let behind = Vector3::new(6.0, 4.0, 12.0);
+        assert!(vp
+            .world_to_screen_with_depth(&behind, screen)
+            .1
+            .is_infinite());
+        assert!((vp.matrix() * vp.inverse() - Matrix4::identity()).amax() < 1e-4);
+
+        // Skipping the inverse up front still inverts on demand
+        let forward = ViewProjection::from_camera_forward_only(&camera, WIDE, 0.1, 100.0);
+        assert_eq!(forward.matrix(), vp.matrix());
+        assert_eq!(forward.inverse(), vp.inverse());
+    }
+
+    #[test]
+    fn test_screen_bounds_of_box_in_front() {
+        let camera = Camera3D::perspective(
+            Vector3::new(0.0f32, 0.0, 10.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        let screen = Vector2::new(1280.0, 720.0);
+        let bbox = BoundingBox::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        let (lo, hi) = camera
+            .screen_bounds_of_box(&bbox, screen, WIDE, 0.1, 100.0)
+            .unwrap();
+        assert!(((lo + hi) / 2.0 - screen / 2.0).norm() < 1e-2);
+
+        // The nearest face sets the size
+        let near_corner =
+            camera.world_to_screen(Vector3::new(1.0, -1.0, 1.0), screen, WIDE, 0.1, 100.0);
+        assert!((hi - near_corner).norm() < 1e-2);
+    }
+
+    #[test]
+    fn test_screen_bounds_of_box_straddling_near_plane() {
+        let camera = Camera3D::perspective(Vector3::zeros(), -Vector3::z(), Vector3::y(), 60.0);
+        let screen = Vector2::new(1280.0, 720.0);
+        // Off to the right and reaching behind the camera
+        let bbox = BoundingBox::new(Vector3::new(1.0, -1.0, -4.0), Vector3::new(3.0, 1.0, 2.0));
+        let (lo, hi) = camera
+            .screen_bounds_of_box(&bbox, screen, WIDE, 0.1, 100.0)
+            .unwrap();
+        assert!(lo.x.is_finite() && lo.y.is_finite() && hi.x.is_finite() && hi.y.is_finite());
+        // Corners behind the camera would project mirrored onto the left half
+        assert!(lo.x > screen.x / 2.0);
+        assert!(lo.y < 0.0 && hi.y > screen.y);
+
+        let behind = BoundingBox::new(Vector3::new(-1.0, -1.0, 1.0), Vector3::new(1.0, 1.0, 3.0));
+        assert_eq!(
+            camera.screen_bounds_of_box(&behind, screen, WIDE, 0.1, 100.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_near_cursor() {
+        let camera = camera();
+        let lens = CameraLens::default();
+        let screen = Vector2::new(1280.0, 720.0);
+        let point = Vector3::new(0.2, 0.1, -1.0);
+        let under = camera.world_to_screen(point, screen, WIDE, 0.01, 1000.0);
+        assert!(camera.is_near_cursor(point, under, 0.0, screen, WIDE, &lens));
+
+        let cursor = under + Vector2::new(6.0, 8.0);
+        assert!(camera.is_near_cursor(point, cursor, 10.5, screen, WIDE, &lens));
+        assert!(!camera.is_near_cursor(point, cursor, 9.5, screen, WIDE, &lens));
+
+        // Behind the camera, projecting onto the middle of the screen
+        let behind = Vector3::new(0.0, 0.0, 3.0);
+        assert!(!camera.is_near_cursor(behind, screen / 2.0, 50.0, screen, WIDE, &lens));
+    }
+
+    #[test]
+    fn test_screen_to_ray_center() {
+        let camera = camera();
+        let screen = Vector2::new(1280.0, 720.0);
+        let ray = camera.screen_to_ray(screen / 2.0, screen);
+        assert!((ray.dir - camera.forward()).norm() < 1e-6);
+        // A ray through a projected point passes back through it
+        let point = Vector3::new(0.3, -0.2, -2.0);
+        let on_screen = camera.world_to_screen(point, screen, WIDE, 0.01, 1000.0);
+        let ray = camera.screen_to_ray(on_screen, screen);
+        let to_point = (Point3::from(point) - ray.origin).normalize();
+        assert!((to_point - ray.dir).norm() < 1e-4);
+    }
+
+    #[test]
+    fn test_screen_corners_on_plane() {
+        let screen = Vector2::new(800.0, 600.0);
+        let tilted = Camera3D::perspective(
+            Vector3::new(0.0f32, 10.0, 0.0),
+            Vector3::new(0.0, 0.0, 4.0),
+            Vector3::y(),
+            60.0,
+        );
+        let [top_left, top_right, bottom_right, bottom_left] =
+            tilted.screen_corners_on_plane(screen, 0.0).unwrap();
+        assert!([top_left, top_right, bottom_right, bottom_left]
+            .iter()
+            .all(|c| c.y == 0.0));
+        // The far edge is further away and wider than the near edge
+        assert!(top_left.z > bottom_left.z);
+        let far_width = (top_left - top_right).norm();
+        let near_width = (bottom_left - bottom_right).norm();
+        assert!(far_width > near_width * 1.1);
+        // Facing +Z the screen's left is +X
+        assert!(top_left.x > top_right.x);
+
+        let level = Camera3D::perspective(
+            Vector3::new(0.0f32, 2.0, 0.0),
+            Vector3::new(0.0, 2.0, 5.0),
+            Vector3::y(),
+            60.0,
+        );
+        assert!(level.screen_corners_on_plane(screen, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_frustum_edges() {
+        let mut edges: Vec<(usize, usize)> = FRUSTUM_EDGES
+            .iter()
+            .map(|&(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        edges.sort();
+        edges.dedup();
+        assert_eq!(edges.len(), 12);
+        for corner in 0..8 {
+            let degree = edges
+                .iter()
+                .filter(|&&(a, b)| a == corner || b == corner)
+                .count();
+            assert_eq!(degree, 3, "corner {} should touch 3 edges", corner);
+        }
+    }
+
+    #[test]
+    fn test_frustum_corners() {
+        // Looking down -Z from z = 1
+        let corners = camera().frustum_corners(WIDE, 0.5, 10.0);
+        for corner in &corners[..4] {
+            assert!((corner.z - 0.5).abs() < 1e-5);
+        }
+        for corner in &corners[4..] {
+            assert!((corner.z + 9.0).abs() < 1e-4);
+        }
+        let far_height = corners[7].y - corners[4].y;
+        let far_width = corners[5].x - corners[4].x;
+        assert!((far_height - 2.0 * 10.0 * deg_to_rad(30.0f32).tan()).abs() < 1e-3);
+        assert!((far_width / far_height - WIDE).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_lens_clip_planes() {
+        let c = camera();
+        let default = c.get_projection_matrix_default(WIDE);
+        assert_eq!(default, c.get_projection_matrix(WIDE, 0.01, 1000.0));
+
+        let lens = CameraLens::new(1.0, 50.0);
+        let custom = c.get_projection_matrix_with_lens(WIDE, &lens);
+        assert_eq!(custom, c.get_projection_matrix(WIDE, 1.0, 50.0));
+        assert_ne!(custom, default);
+    }
+
+    #[test]
+    fn test_blend_projection_endpoints() {
+        let c = Camera3D::perspective(
+            Vector3::new(0.0, 0.0, 10.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        let perspective = c.blend_projection_matrix(0.0, WIDE, 0.1, 100.0);
+        assert_eq!(perspective, c.get_projection_matrix(WIDE, 0.1, 100.0));
+
+        let height = 2.0 * 10.0 * deg_to_rad(30.0f32).tan();
+        let ortho_camera = Camera3D::orthographic(c.position, c.target, c.up, height);
+        let orthographic = c.blend_projection_matrix(1.0, WIDE, 0.1, 100.0);
+        let expected = ortho_camera.get_projection_matrix(WIDE, 0.1, 100.0);
+        assert!((orthographic - expected).iter().all(|x| x.abs() < 1e-5));
+
+        let half = c.blend_projection_matrix(0.5, WIDE, 0.1, 100.0);
+        for i in 0..16 {
+            let (lo, hi) = (
+                perspective[i].min(orthographic[i]),
+                perspective[i].max(orthographic[i]),
+            );
+            assert!(half[i] >= lo - 1e-6 && half[i] <= hi + 1e-6);
+        }
+        assert!(half != perspective && half != orthographic);
+    }
+
+    #[test]
+    fn test_angular_size_falls_off_with_distance() {
+        let c = camera();
+        let near = c.angular_size(Vector3::new(0.0, 0.0, -99.0), 1.0);
+        let far = c.angular_size(Vector3::new(0.0, 0.0, -199.0), 1.0);
+        assert!((near / far - 2.0).abs() < 1e-3);
+        assert_eq!(
+            c.angular_size(Vector3::new(0.0, 0.0, 0.5), 1.0),
+            std::f32::consts::PI
+        );
+
+        let pixels = c.screen_radius(Vector3::new(0.0, 0.0, -9.0), 1.0, 720.0);
+        let expected = 1.0 / (10.0 * deg_to_rad(30.0f32).tan()) * 360.0;
+        assert!((pixels - expected).abs() < 0.5);
+        assert_eq!(c.screen_radius(Vector3::zeros(), 2.0, 720.0), 720.0);
+    }
+
+    #[test]
+    fn test_letterboxed_projection() {
+        let c = camera();
+        let full = c.get_projection_matrix(WIDE, 0.1, 100.0);
+        let none = c.get_projection_matrix_letterboxed(WIDE, 0.0, 0.1, 100.0);
+        assert!((none - full).iter().all(|x| x.abs() < 1e-5));
+
+        let bars = c.get_projection_matrix_letterboxed(WIDE, 0.25, 0.1, 100.0);
+        assert!((bars[(0, 0)] - full[(0, 0)]).abs() < 1e-5);
+        // A quarter of the height is covered, so the vertical view shrinks to three quarters
+        assert!((full[(1, 1)] / bars[(1, 1)] - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_frame_targets_fit() {
+        let mut c = camera();
+        let mut targets = vec![
+            Vector3::new(-5.0, 0.0, 0.0),
+            Vector3::new(5.0, 1.0, 2.0),
+            Vector3::new(0.0, -2.0, -3.0),
+        ];
+        let direction = Vector3::new(0.0, -1.0, -1.0);
+        let screen = Vector2::new(1280.0, 720.0);
+        c.frame_targets(&targets, direction, WIDE, 0.5);
+        for target in &targets {
+            let (pos, depth) = c.world_to_screen_with_depth(*target, screen, WIDE, 0.1, 1000.0);
+            assert!(depth > 0.0 && depth < 1.0);
+            assert!(pos.x >= 0.0 && pos.x <= screen.x && pos.y >= 0.0 && pos.y <= screen.y);
+        }
+        assert!((c.forward() - direction.normalize()).norm() < 1e-5);
+        let near = (c.position - c.target).norm();
+
+        targets.push(Vector3::new(40.0, 0.0, 0.0));
+        c.frame_targets(&targets, direction, WIDE, 0.5);
+        assert!((c.position - c.target).norm() > near);
+    }
+}
@@ -0,0 +1,815 @@
+//! Layering effects on top of a [`Camera3D`]
+use nalgebra::{Scalar, Unit, UnitQuaternion, Vector3};
+use num_traits::Float;
+
+use crate::core::camera::Camera3D;
+use crate::core::math::{deg_to_rad, vec3_from_f32, vec3_to_f32};
+use crate::ease::{linear_none, EaseFn};
+
+/// An effect applied on top of a camera, such as shake or head-bob.
+pub trait CameraModifier<T>
+where
+    T: Float,
+{
+    /// Advances the effect by `dt` seconds and applies it to `cam`.
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32);
+
+    /// Receives the player's shake preferences from [`CameraRig::set_shake_settings`]. Only shakes need to care, everything else can ignore it.
+    fn set_shake_settings(&mut self, _settings: ShakeSettings) {}
+}
+
+/// The player's preference for how strong camera shake is, for accessibility.
+///
+/// Every shake scales its effect by `intensity_scale`. 1 is full strength, and 0 turns shaking off completely for players prone to motion sickness.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShakeSettings {
+    pub intensity_scale: f32,
+}
+
+impl ShakeSettings {
+    /// Returns true if shaking is turned off.
+    pub fn disabled(&self) -> bool {
+        self.intensity_scale <= 0.0
+    }
+}
+
+impl Default for ShakeSettings {
+    fn default() -> Self {
+        ShakeSettings {
+            intensity_scale: 1.0,
+        }
+    }
+}
+
+/// A base camera with an ordered stack of [`CameraModifier`]s on top.
+///
+/// Gameplay code moves `base`, and [`CameraRig::output`] returns the camera to render with. Modifiers never touch `base`, so removing one removes its effect.
+pub struct CameraRig<T>
+where
+    T: Float,
+{
+    pub base: Camera3D<T>,
+    modifiers: Vec<Box<dyn CameraModifier<T>>>,
+    shake_settings: ShakeSettings,
+}
+
+impl<T> CameraRig<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a rig with no modifiers.
+    pub fn new(base: Camera3D<T>) -> Self {
+        CameraRig {
+            base,
+            modifiers: Vec::new(),
+            shake_settings: ShakeSettings::default(),
+        }
+    }
+
+    /// Adds a modifier on top of the existing ones and returns its index.
+    ///
+    /// The modifier is given the rig's current [`ShakeSettings`].
+    pub fn push(&mut self, modifier: impl CameraModifier<T> + 'static) -> usize {
+        let mut modifier = Box::new(modifier);
+        modifier.set_shake_settings(self.shake_settings);
+        self.modifiers.push(modifier);
+        self.modifiers.len() - 1
+    }
+
+    /// Sets how strong shakes are for every modifier, now and added later.
+    pub fn set_shake_settings(&mut self, settings: ShakeSettings) {
+        self.shake_settings = settings;
+        for modifier in self.modifiers.iter_mut() {
+            modifier.set_shake_settings(settings);
+        }
+    }
+
+    /// Returns the current shake settings.
+    pub fn shake_settings(&self) -> ShakeSettings {
+        self.shake_settings
+    }
+
+    /// Removes the modifier at `index`, shifting the ones after it down.
+    pub fn remove(&mut self, index: usize) -> Box<dyn CameraModifier<T>> {
+        self.modifiers.remove(index)
+    }
+
+    /// Removes every modifier.
+    pub fn clear(&mut self) {
+        self.modifiers.clear();
+    }
+
+    /// Returns the number of modifiers.
+    pub fn len(&self) -> usize {
+        self.modifiers.len()
+    }
+
+    /// Returns true if there are no modifiers.
+    pub fn is_empty(&self) -> bool {
+        self.modifiers.is_empty()
+    }
+
+    /// Advances every modifier by `dt` seconds and returns `base` with all of them applied, in the order they were added.
+    pub fn output(&mut self, dt: f32) -> Camera3D<T> {
+        let mut cam = self.base;
+        for modifier in self.modifiers.iter_mut() {
+            modifier.apply(&mut cam, dt);
+        }
+        cam
+    }
+}
+
+/// Smooth pseudo-random wobble in `[-1, 1]`.
+fn wobble(t: f32, seed: f32) -> f32 {
+    0.6 * (t + seed).sin() + 0.4 * (2.3 * t + 1.7 * seed).sin()
+}
+
+/// Trauma-based positional camera shake.
+///
+/// Impacts add trauma with [`ScreenShake::add_trauma`], which then falls to zero over `peak / decay` seconds following `decay_curve`. The camera is offset along its right and up axes by up to `max_offset` world units, scaled by trauma squared so small hits stay subtle.
+/// `decay_curve` is any of the [`ease`](crate::ease) functions, easing from the peak trauma down to zero. The default [`linear_none`] loses `decay` trauma per second, [`cubic_out`](crate::ease::cubic_out) drops off fast then lingers, and [`cubic_in`](crate::ease::cubic_in) holds the hit before fading.
+/// Trauma can only be raised through [`ScreenShake::add_trauma`], which keeps the decay curve in step with it. [`RotationalShake`] works the same way.
+#[derive(Debug, Clone)]
+pub struct ScreenShake {
+    pub decay: f32,
+    pub decay_curve: EaseFn,
+    pub max_offset: f32,
+    pub frequency: f32,
+    pub settings: ShakeSettings,
+    trauma: f32,
+    peak: f32,
+    elapsed: f32,
+    time: f32,
+}
+
+impl ScreenShake {
+    /// Creates a shake with no trauma, decaying linearly at 1 per second.
+    pub fn new(max_offset: f32) -> Self {
+        ScreenShake {
+            decay: 1.0,
+            decay_curve: linear_none,
+            max_offset,
+            frequency: 25.0,
+            settings: ShakeSettings::default(),
+            trauma: 0.0,
+            peak: 0.0,
+            elapsed: 0.0,
+            time: 0.0,
+        }
+    }
+
+    /// Returns the current trauma, between 0 and 1.
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Adds trauma, capped at 1. The decay starts over from the new total.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).max(0.0).min(1.0);
+        self.peak = self.trauma;
+        self.elapsed = 0.0;
+    }
+
+    /// Returns how long the current trauma takes to reach zero, in seconds.
+    pub fn duration(&self) -> f32 {
+        if self.decay > 0.0 {
+            self.peak / self.decay
+        } else {
+            f32::INFINITY
+        }
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.elapsed += dt;
+        let duration = self.duration();
+        self.trauma = if self.elapsed >= duration {
+            0.0
+        } else {
+            (self.decay_curve)(self.elapsed, self.peak, -self.peak, duration)
+                .max(0.0)
+                .min(1.0)
+        };
+    }
+}
+
+impl<T> CameraModifier<T> for ScreenShake
+where
+    T: Float + Scalar,
+{
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32) {
+        self.time += dt;
+        let strength = self.max_offset * self.trauma * self.trauma * self.settings.intensity_scale;
+        self.advance(dt);
+        if strength <= 0.0 || self.settings.disabled() {
+            return;
+        }
+
+        let (_, right, up) = cam.basis();
+        let t = self.time * self.frequency;
+        let offset = (right * wobble(t, 0.0) + up * wobble(t, 10.0)) * strength;
+        cam.position = vec3_from_f32(&(vec3_to_f32(&cam.position) + offset));
+        cam.target = vec3_from_f32(&(vec3_to_f32(&cam.target) + offset));
+    }
+
+    fn set_shake_settings(&mut self, settings: ShakeSettings) {
+        self.settings = settings;
+    }
+}
+
+/// Trauma-based camera shake that turns the view instead of moving it.
+///
+/// Works like [`ScreenShake`], but swings `target` around the camera by up to `max_angle` degrees and leaves `position` alone, so the camera can't be shaken through a nearby wall.
+/// Set `max_roll` to also tilt `up` by up to that many degrees.
+#[derive(Debug, Clone)]
+pub struct RotationalShake {
+    pub decay: f32,
+    pub max_angle: f32,
+    pub max_roll: f32,
+    pub frequency: f32,
+    pub settings: ShakeSettings,
+    trauma: f32,
+    time: f32,
+}
+
+impl RotationalShake {
+    /// Creates a shake with no trauma and no roll, decaying fully in one second.
+    pub fn new(max_angle: f32) -> Self {
+        RotationalShake {
+            trauma: 0.0,
+            decay: 1.0,
+            max_angle,
+            max_roll: 0.0,
+            frequency: 25.0,
+            settings: ShakeSettings::default(),
+            time: 0.0,
+        }
+    }
+
+    /// Returns the current trauma, between 0 and 1.
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Adds trauma, capped at 1.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).max(0.0).min(1.0);
+    }
+}
+
+impl<T> CameraModifier<T> for RotationalShake
+where
+    T: Float + Scalar,
+{
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32) {
+        self.time += dt;
+        let shake = self.trauma * self.trauma * self.settings.intensity_scale;
+        self.trauma = (self.trauma - self.decay * dt).max(0.0);
+        if shake <= 0.0 || self.settings.disabled() {
+            return;
+        }
+
+        let (forward, right, up) = cam.basis();
+        let t = self.time * self.frequency;
+        let yaw = deg_to_rad(self.max_angle * shake * wobble(t, 0.0));
+        let pitch = deg_to_rad(self.max_angle * shake * wobble(t, 10.0));
+        let roll = deg_to_rad(self.max_roll * shake * wobble(t, 20.0));
+        let rotation = UnitQuaternion::from_axis_angle(&Unit::new_unchecked(up), yaw)
+            * UnitQuaternion::from_axis_angle(&Unit::new_unchecked(right), pitch);
+
+        let position = vec3_to_f32(&cam.position);
+        let offset = vec3_to_f32(&cam.target) - position;
+        cam.target = vec3_from_f32(&(position + rotation * offset));
+        if roll != 0.0 {
+            let forward = rotation * forward;
+            let up = UnitQuaternion::from_axis_angle(&Unit::new_normalize(forward), roll)
+                * (rotation * up);
+            cam.up = vec3_from_f32(&up);
+        }
+    }
+
+    fn set_shake_settings(&mut self, settings: ShakeSettings) {
+        self.settings = settings;
+    }
+}
+
+/// Weapon recoil, kicking the view up and to the side on every shot and easing back afterwards.
+///
+/// Unlike the shakes this isn't continuous: each [`Recoil::kick`] adds to the offset straight away, so rapid shots stack up (to at most `max_pitch` degrees), and the offset then recovers toward zero at the exponential rate `recovery`.
+/// Positive yaw kicks to the left, like [`Camera3D::yaw_pitch`]. Set `punch` to also push the camera back by that many world units per degree of pitch.
+#[derive(Debug, Clone)]
+pub struct Recoil {
+    pub recovery: f32,
+    pub max_pitch: f32,
+    pub punch: f32,
+    pitch: f32,
+    yaw: f32,
+}
+
+impl Recoil {
+    /// Creates a recoil with no offset, recovering at a rate of 8 and capped at 30 degrees.
+    pub fn new() -> Self {
+        Recoil {
+            recovery: 8.0,
+            max_pitch: 30.0,
+            punch: 0.0,
+            pitch: 0.0,
+            yaw: 0.0,
+        }
+    }
+
+    /// Adds a kick of `pitch_deg` up and `yaw_deg` to the side.
+    pub fn kick(&mut self, pitch_deg: f32, yaw_deg: f32) {
+        self.pitch = (self.pitch + pitch_deg)
+            .max(-self.max_pitch)
+            .min(self.max_pitch);
+        self.yaw += yaw_deg;
+    }
+
+    /// Returns the current offset as pitch and yaw in degrees.
+    pub fn offset(&self) -> (f32, f32) {
+        (self.pitch, self.yaw)
+    }
+}
+
+impl Default for Recoil {
+    fn default() -> Self {
+        Recoil::new()
+    }
+}
+
+impl<T> CameraModifier<T> for Recoil
+where
+    T: Float + Scalar,
+{
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32) {
+        let (pitch, yaw) = (self.pitch, self.yaw);
+        let settle = (-self.recovery * dt).exp();
+        // Snap the tail of the recovery so the view ends up exactly where it started
+        let recover = |angle: f32| {
+            let angle = angle * settle;
+            if angle.abs() < 1e-3 {
+                0.0
+            } else {
+                angle
+            }
+        };
+        self.pitch = recover(self.pitch);
+        self.yaw = recover(self.yaw);
+        if pitch == 0.0 && yaw == 0.0 {
+            return;
+        }
+
+        let (forward, right, up) = cam.basis();
+        let rotation = UnitQuaternion::from_axis_angle(&Unit::new_unchecked(up), deg_to_rad(yaw))
+            * UnitQuaternion::from_axis_angle(&Unit::new_unchecked(right), deg_to_rad(pitch));
+        let position = vec3_to_f32(&cam.position) - forward * self.punch * pitch;
+        let offset = vec3_to_f32(&cam.target) - vec3_to_f32(&cam.position);
+        cam.position = vec3_from_f32(&position);
+        cam.target = vec3_from_f32(&(position + rotation * offset));
+        cam.up = vec3_from_f32(&(rotation * up));
+    }
+}
+
+/// Shifts the camera ahead of a moving subject, the 3D counterpart of [`Lookahead2D`](crate::core::camera::Lookahead2D).
+///
+/// Set `velocity` every frame. The offset eases toward `max_offset` world units in the direction of travel at the exponential rate `smoothing`, and back to zero once the subject stops.
+#[derive(Debug, Clone)]
+pub struct Lookahead<T>
+where
+    T: Float,
+{
+    pub velocity: Vector3<T>,
+    pub max_offset: f32,
+    pub smoothing: f32,
+    offset: Vector3<f32>,
+}
+
+impl<T> Lookahead<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a look-ahead with no velocity.
+    pub fn new(max_offset: f32, smoothing: f32) -> Self {
+        Lookahead {
+            velocity: Vector3::new(T::zero(), T::zero(), T::zero()),
+            max_offset,
+            smoothing,
+            offset: Vector3::zeros(),
+        }
+    }
+
+    /// Returns the offset currently applied.
+    pub fn offset(&self) -> Vector3<f32> {
+        self.offset
+    }
+}
+
+impl<T> CameraModifier<T> for Lookahead<T>
+where
+    T: Float + Scalar,
+{
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32) {
+        let desired = vec3_to_f32(&self.velocity)
+            .try_normalize(f32::EPSILON)
+            .map(|dir| dir * self.max_offset)
+            .unwrap_or_else(Vector3::zeros);
+        self.offset += (desired - self.offset) * (1.0 - (-self.smoothing * dt).exp());
+        cam.position = vec3_from_f32(&(vec3_to_f32(&cam.position) + self.offset));
+        cam.target = vec3_from_f32(&(vec3_to_f32(&cam.target) + self.offset));
+    }
+}
+
+/// Walking head-bob for first-person cameras.
+///
+/// The head sways sideways once per cycle and dips twice, tracing a figure-eight. `speed` is the walk speed relative to the pace the bob is tuned for, so 0 stands still and 1 bobs at `frequency` cycles per second with the full amplitudes.
+#[derive(Debug, Clone)]
+pub struct HeadBob {
+    pub vertical_amplitude: f32,
+    pub lateral_amplitude: f32,
+    pub frequency: f32,
+    speed: f32,
+    phase: f32,
+}
+
+impl HeadBob {
+    /// Creates a bob that is standing still.
+    pub fn new(vertical_amplitude: f32, lateral_amplitude: f32, frequency: f32) -> Self {
+        HeadBob {
+            vertical_amplitude,
+            lateral_amplitude,
+            frequency,
+            speed: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    /// Sets the walk speed. Negative values are treated as 0.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Returns the walk speed.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Returns the current lateral and vertical offsets, in world units.
+    pub fn offset(&self) -> (f32, f32) {
+        let weight = self.speed.min(1.0);
+        (
+            self.phase.sin() * self.lateral_amplitude * weight,
+            (2.0 * self.phase).sin() * self.vertical_amplitude * weight,
+        )
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.phase = (self.phase + std::f32::consts::TAU * self.frequency * self.speed * dt)
+            % std::f32::consts::TAU;
+    }
+}
+
+impl<T> CameraModifier<T> for HeadBob
+where
+    T: Float + Scalar,
+{
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32) {
+        self.advance(dt);
+        let (lateral, vertical) = self.offset();
+        if lateral == 0.0 && vertical == 0.0 {
+            return;
+        }
+
+        let (_, right, up) = cam.basis();
+        let offset = right * lateral + up * vertical;
+        cam.position = vec3_from_f32(&(vec3_to_f32(&cam.position) + offset));
+        cam.target = vec3_from_f32(&(vec3_to_f32(&cam.target) + offset));
+    }
+}
+
+/// Leans the camera sideways to peek around cover.
+///
+/// At a lean of 1 the camera sits `distance` world units to its right and rolls `max_roll` degrees the same way, -1 leans as far left. The lean eases toward the amount set with [`PeekModifier::set_lean`] at the exponential rate `smoothing`.
+#[derive(Debug, Clone)]
+pub struct PeekModifier {
+    pub distance: f32,
+    pub max_roll: f32,
+    pub smoothing: f32,
+    target: f32,
+    lean: f32,
+}
+
+impl PeekModifier {
+    /// Creates an upright peek that leans 0.5 units and 8 degrees, easing at a rate of 10.
+    pub fn new() -> Self {
+        PeekModifier {
+            distance: 0.5,
+            max_roll: 8.0,
+            smoothing: 10.0,
+            target: 0.0,
+            lean: 0.0,
+        }
+    }
+
+    /// Sets how far to lean, from -1 (fully left) to 1 (fully right). 0 stands back up.
+    pub fn set_lean(&mut self, amount: f32) {
+        self.target = amount.max(-1.0).min(1.0);
+    }
+
+    /// Returns the lean currently applied.
+    pub fn lean(&self) -> f32 {
+        self.lean
+    }
+}
+
+impl Default for PeekModifier {
+    fn default() -> Self {
+        PeekModifier::new()
+    }
+}
+
+impl<T> CameraModifier<T> for PeekModifier
+where
+    T: Float + Scalar,
+{
+    fn apply(&mut self, cam: &mut Camera3D<T>, dt: f32) {
+        let remaining = (self.target - self.lean) * (-self.smoothing * dt).exp();
+        // Snap the tail so standing back up restores the pose exactly
+        self.lean = if remaining.abs() < 1e-4 {
+            self.target
+        } else {
+            self.target - remaining
+        };
+        if self.lean == 0.0 {
+            return;
+        }
+
+        let (forward, right, up) = cam.basis();
+        let offset = right * self.lean * self.distance;
+        let roll = UnitQuaternion::from_axis_angle(
+            &Unit::new_unchecked(forward),
+            deg_to_rad(self.lean * self.max_roll),
+        );
+        cam.position = vec3_from_f32(&(vec3_to_f32(&cam.position) + offset));
+        cam.target = vec3_from_f32(&(vec3_to_f32(&cam.target) + offset));
+        cam.up = vec3_from_f32(&(roll * up));
+    }
+}
+
+#[cfg(test)]
+mod test_rig {
+    use super::*;
+
+    struct Scale(f32);
+    impl CameraModifier<f32> for Scale {
+        fn apply(&mut self, cam: &mut Camera3D<f32>, _dt: f32) {
+            cam.position *= self.0;
+        }
+    }
+
+    struct Shift(f32);
+    impl CameraModifier<f32> for Shift {
+        fn apply(&mut self, cam: &mut Camera3D<f32>, _dt: f32) {
+            cam.position.x += self.0;
+        }
+    }
+
+    fn camera() -> Camera3D<f32> {
+        Camera3D::perspective(
+            Vector3::new(1.0, 0.0, 5.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        )
+    }
+
+    #[test]
+    fn test_modifiers_apply_in_order() {
+        let mut rig = CameraRig::new(camera());
+        rig.push(Scale(2.0));
+        rig.push(Shift(1.0));
+        assert_eq!(rig.output(0.0).position.x, 3.0);
+
+        let mut rig = CameraRig::new(camera());
+        rig.push(Shift(1.0));
+        rig.push(Scale(2.0));
+        assert_eq!(rig.output(0.0).position.x, 4.0);
+        assert_eq!(rig.base.position.x, 1.0, "the base camera must not change");
+    }
+
+    #[test]
+    fn test_removing_modifier_reverts() {
+        let mut rig = CameraRig::new(camera());
+        let mut shake = ScreenShake::new(0.5);
+        shake.add_trauma(1.0);
+        let index = rig.push(shake);
+        let shaken = rig.output(0.05);
+        assert!((shaken.position - rig.base.position).norm() > 0.0);
+
+        rig.remove(index);
+        assert!(rig.is_empty());
+        assert_eq!(rig.output(0.05).position, rig.base.position);
+    }
+
+    #[test]
+    fn test_shake_decays() {
+        let mut cam = camera();
+        let mut shake = ScreenShake::new(0.5);
+        shake.add_trauma(1.0);
+        for _ in 0..30 {
+            CameraModifier::<f32>::apply(&mut shake, &mut cam, 1.0 / 20.0);
+        }
+        assert_eq!(shake.trauma(), 0.0);
+        let mut settled = camera();
+        CameraModifier::<f32>::apply(&mut shake, &mut settled, 1.0 / 20.0);
+        assert_eq!(settled.position, camera().position);
+    }
+
+    #[test]
+    fn test_shake_intensity_scale() {
+        let offset = |scale: f32| {
+            let mut rig = CameraRig::new(camera());
+            let mut shake = ScreenShake::new(0.5);
+            shake.decay = 0.0;
+            shake.add_trauma(1.0);
+            rig.push(shake);
+            let mut rotational = RotationalShake::new(10.0);
+            rotational.decay = 0.0;
+            rotational.add_trauma(1.0);
+            rig.set_shake_settings(ShakeSettings {
+                intensity_scale: scale,
+            });
+            // Settings given before pushing reach the new modifier too
+            rig.push(rotational);
+            let cam = rig.output(0.13);
+            (
+                (cam.position - camera().position).norm(),
+                (cam.target - camera().target).norm(),
+            )
+        };
+
+        assert_eq!(offset(0.0), (0.0, 0.0));
+        let (full, _) = offset(1.0);
+        let (half, _) = offset(0.5);
+        assert!(full > 0.0);
+        assert!((half * 2.0 - full).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_head_bob_still_at_zero_speed() {
+        let mut bob = HeadBob::new(0.1, 0.05, 2.0);
+        let mut cam = camera();
+        for _ in 0..10 {
+            CameraModifier::<f32>::apply(&mut bob, &mut cam, 0.1);
+        }
+        assert_eq!(bob.offset(), (0.0, 0.0));
+        assert_eq!(cam.position, camera().position);
+    }
+
+    #[test]
+    fn test_head_bob_vertical_twice_lateral() {
+        let mut bob = HeadBob::new(0.1, 0.05, 1.0);
+        bob.set_speed(1.0);
+        // Start between samples so no sample lands on a zero crossing
+        bob.advance(0.0005);
+        let (mut lateral_crossings, mut vertical_crossings) = (0, 0);
+        let (mut last_lateral, mut last_vertical) = bob.offset();
+        // Two full lateral cycles
+        for _ in 0..2000 {
+            bob.advance(0.001);
+            let (lateral, vertical) = bob.offset();
+            if lateral.signum() != last_lateral.signum() {
+                lateral_crossings += 1;
+            }
+            if vertical.signum() != last_vertical.signum() {
+                vertical_crossings += 1;
+            }
+            last_lateral = lateral;
+            last_vertical = vertical;
+        }
+        assert_eq!(lateral_crossings, 4);
+        assert_eq!(vertical_crossings, 8);
+    }
+
+    #[test]
+    fn test_rotational_shake_keeps_position() {
+        use crate::core::math::rad_to_deg;
+
+        let angle = |trauma: f32| {
+            let mut shake = RotationalShake::new(10.0);
+            shake.decay = 0.0;
+            shake.max_roll = 5.0;
+            shake.add_trauma(trauma);
+            let mut cam = camera();
+            CameraModifier::<f32>::apply(&mut shake, &mut cam, 0.13);
+            assert_eq!(cam.position, camera().position);
+            let before = (camera().target - camera().position).normalize();
+            let after = (cam.target - cam.position).normalize();
+            rad_to_deg(before.dot(&after).min(1.0).acos())
+        };
+
+        let full = angle(1.0);
+        assert!(full > 0.0 && full <= 10.0 * 2.0f32.sqrt());
+        // Scaled by trauma squared
+        assert!((angle(0.5) * 4.0 - full).abs() < full * 0.01);
+        assert_eq!(angle(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_recoil_peaks_then_recovers() {
+        let pitch = |cam: &Camera3D<f32>| cam.yaw_pitch().1 - camera().yaw_pitch().1;
+        let mut recoil = Recoil::new();
+        recoil.kick(5.0, 0.0);
+
+        let mut cam = camera();
+        CameraModifier::<f32>::apply(&mut recoil, &mut cam, 0.016);
+        assert!((pitch(&cam) - 5.0).abs() < 1e-3);
+        assert!(recoil.offset().0 < 5.0);
+
+        for _ in 0..200 {
+            CameraModifier::<f32>::apply(&mut recoil, &mut camera(), 0.016);
+        }
+        assert_eq!(recoil.offset(), (0.0, 0.0));
+        let mut cam = camera();
+        CameraModifier::<f32>::apply(&mut recoil, &mut cam, 0.016);
+        assert_eq!(cam.target, camera().target);
+    }
+
+    #[test]
+    fn test_recoil_kicks_stack() {
+        let mut recoil = Recoil::new();
+        recoil.max_pitch = 12.0;
+        recoil.kick(4.0, 1.0);
+        recoil.kick(4.0, -0.5);
+        assert_eq!(recoil.offset(), (8.0, 0.5));
+        recoil.kick(4.0, 0.0);
+        recoil.kick(4.0, 0.0);
+        assert_eq!(recoil.offset().0, 12.0);
+    }
+
+    #[test]
+    fn test_peek_leans_and_returns() {
+        use crate::core::math::rad_to_deg;
+
+        let base = camera();
+        let (_, right, up) = base.basis();
+        let step = |peek: &mut PeekModifier| {
+            for _ in 0..119 {
+                CameraModifier::<f32>::apply(peek, &mut camera(), 0.016);
+            }
+            let mut cam = camera();
+            CameraModifier::<f32>::apply(peek, &mut cam, 0.016);
+            cam
+        };
+
+        let mut peek = PeekModifier::new();
+        peek.set_lean(1.0);
+        let leaned = step(&mut peek);
+        assert!((leaned.position - base.position - right * 0.5).norm() < 1e-3);
+        // Rolled a little toward the right, still looking the same way
+        let roll = rad_to_deg(leaned.up.angle(&up));
+        assert!((roll - 8.0).abs() < 1e-2 && leaned.up.dot(&right) > 0.0);
+        assert!((leaned.forward() - base.forward()).norm() < 1e-5);
+
+        peek.set_lean(0.0);
+        let restored = step(&mut peek);
+        assert_eq!(peek.lean(), 0.0);
+        assert_eq!(restored.position, base.position);
+        assert_eq!(restored.up, base.up);
+    }
+
+    #[test]
+    fn test_shake_decay_curves() {
+        use crate::ease::{cubic_in, cubic_out};
+
+        let curves: [EaseFn; 3] = [linear_none, cubic_out, cubic_in];
+        for &curve in &curves {
+            let mut shake = ScreenShake::new(0.5);
+            shake.decay = 2.0;
+            shake.decay_curve = curve;
+            shake.add_trauma(0.8);
+            assert!((shake.duration() - 0.4).abs() < 1e-6);
+
+            for _ in 0..39 {
+                shake.advance(0.01);
+                let expected = curve(shake.elapsed, 0.8, -0.8, 0.4);
+                assert!((shake.trauma() - expected).abs() < 1e-5);
+            }
+            assert!(shake.trauma() > 0.0);
+            shake.advance(0.02);
+            assert_eq!(shake.trauma(), 0.0);
+        }
+
+        // Half way through, ease-out has shed most of the trauma and ease-in very little
+        let halfway = |curve: EaseFn| {
+            let mut shake = ScreenShake::new(0.5);
+            shake.decay_curve = curve;
+            shake.add_trauma(1.0);
+            shake.advance(0.5);
+            shake.trauma()
+        };
+        assert!(halfway(cubic_out) < halfway(linear_none));
+        assert!(halfway(linear_none) < halfway(cubic_in));
+        assert!((halfway(linear_none) - 0.5).abs() < 1e-5);
+    }
+}
@@ -1,7 +1,116 @@
 //! Common collision handling code
+use nalgebra::{Scalar, Vector3};
+use num_traits::Float;
+use parry3d::query::Ray;
+
+use crate::core::math::{vec3_from_f32, vec3_to_f32, ToFFIVector};
 use crate::core::models::Model;
 use crate::ffi;
 
+/// An axis-aligned box from `min` to `max`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingBox<T>
+where
+    T: Scalar,
+{
+    pub min: Vector3<T>,
+    pub max: Vector3<T>,
+}
+
+impl<T> BoundingBox<T>
+where
+    T: Float + Scalar,
+{
+    /// Creates a box, swapping any components of `min` and `max` that are the wrong way around.
+    pub fn new(min: Vector3<T>, max: Vector3<T>) -> Self {
+        BoundingBox {
+            min: Vector3::new(min.x.min(max.x), min.y.min(max.y), min.z.min(max.z)),
+            max: Vector3::new(min.x.max(max.x), min.y.max(max.y), min.z.max(max.z)),
+        }
+    }
+
+    /// Returns the point in the middle of the box.
+    pub fn center(&self) -> Vector3<T> {
+        vec3_from_f32(&((vec3_to_f32(&self.min) + vec3_to_f32(&self.max)) / 2.0))
+    }
+
+    /// Returns how far along `ray` it first touches the surface of the box, in multiples of the ray's direction.
+    ///
+    /// Only hits in front of the ray's origin count. If the origin is inside the box, this is where the ray leaves it.
+    pub fn ray_intersection(&self, ray: &Ray) -> Option<f32> {
+        let (min, max) = (vec3_to_f32(&self.min), vec3_to_f32(&self.max));
+        let (mut enter, mut exit) = (f32::NEG_INFINITY, f32::INFINITY);
+        for axis in 0..3 {
+            let (origin, dir) = (ray.origin[axis], ray.dir[axis]);
+            if dir.abs() < f32::EPSILON {
+                // Parallel to this pair of faces, so it has to start between them
+                if origin < min[axis] || origin > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let a = (min[axis] - origin) / dir;
+            let b = (max[axis] - origin) / dir;
+            enter = enter.max(a.min(b));
+            exit = exit.min(a.max(b));
+        }
+
+        if exit < enter || exit < 0.0 {
+            None
+        } else if enter >= 0.0 {
+            Some(enter)
+        } else {
+            Some(exit)
+        }
+    }
+}
+
+impl<T> Into<ffi::BoundingBox> for &BoundingBox<T>
+where
+    T: Float + Scalar,
+{
+    fn into(self) -> ffi::BoundingBox {
+        ffi::BoundingBox {
+            min: vec3_to_f32(&self.min).to_ffi_vector(),
+            max: vec3_to_f32(&self.max).to_ffi_vector(),
+        }
+    }
+}
+
+/// Returns where `ray` meets the horizontal plane at height `ground_height`.
+///
+/// Returns `None` if the ray runs parallel to the plane or points away from it.
+pub fn get_collision_ray_ground(ray: &Ray, ground_height: f32) -> Option<Vector3<f32>> {
+    if ray.dir.y.abs() < f32::EPSILON {
+        return None;
+    }
+    let t = (ground_height - ray.origin.y) / ray.dir.y;
+    if t < 0.0 {
+        return None;
+    }
+    let mut point = ray.point_at(t).coords;
+    point.y = ground_height;
+    Some(point)
+}
+
+/// Returns the index of the box `ray` hits first, for picking objects under the cursor.
+///
+/// Boxes behind the ray's origin are ignored. If several boxes are hit at the same distance the one listed first wins.
+pub fn pick_nearest_box<T>(ray: &Ray, boxes: &[BoundingBox<T>]) -> Option<usize>
+where
+    T: Float + Scalar,
+{
+    let mut nearest: Option<(usize, f32)> = None;
+    for (i, b) in boxes.iter().enumerate() {
+        if let Some(t) = b.ray_intersection(ray) {
+            if nearest.map_or(true, |(_, best)| t < best) {
+                nearest = Some((i, t));
+            }
+        }
+    }
+    nearest.map(|(i, _)| i)
+}
+
 // impl Rectangle {
 //     /// Check collision between two rectangles
 //     #[inline]
@@ -167,3 +276,60 @@ use crate::ffi;
 // pub fn get_collision_ray_ground(ray: Ray, ground_height: f32) -> RayHitInfo {
 //     unsafe { ffi::GetCollisionRayGround(ray.into(), ground_height).into() }
 // }
+
+#[cfg(test)]
+mod test_collision {
+    use super::*;
+    use nalgebra::Point3;
+
+    fn unit_box(center: Vector3<f32>) -> BoundingBox<f32> {
+        let half = Vector3::new(0.5, 0.5, 0.5);
+        BoundingBox::new(center - half, center + half)
+    }
+
+    #[test]
+    fn test_pick_nearest_overlapping() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 10.0), Vector3::new(0.0, 0.0, -1.0));
+        let boxes = [
+            unit_box(Vector3::new(0.0, 0.0, 0.0)),
+            // Overlaps the first box but sticks out toward the ray
+            unit_box(Vector3::new(0.2, 0.0, 0.6)),
+            unit_box(Vector3::new(5.0, 0.0, 5.0)),
+        ];
+        assert_eq!(pick_nearest_box(&ray, &boxes), Some(1));
+        assert!((boxes[1].ray_intersection(&ray).unwrap() - 8.9).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_pick_ignores_boxes_behind() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        let boxes = [
+            unit_box(Vector3::new(0.0, 0.0, 3.0)),
+            unit_box(Vector3::new(0.0, 0.0, -3.0)),
+        ];
+        assert_eq!(pick_nearest_box(&ray, &boxes), Some(1));
+        assert_eq!(pick_nearest_box(&ray, &boxes[..1]), None);
+    }
+
+    #[test]
+    fn test_pick_tie_prefers_first() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 10.0), Vector3::new(0.0, 0.0, -1.0));
+        let boxes = [
+            BoundingBox::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(0.0, 1.0, 1.0)),
+            BoundingBox::new(Vector3::new(0.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0)),
+        ];
+        assert_eq!(pick_nearest_box(&ray, &boxes), Some(0));
+    }
+
+    #[test]
+    fn test_ray_ground() {
+        let down = Ray::new(Point3::new(1.0, 10.0, 2.0), Vector3::new(0.0, -2.0, 1.0));
+        let hit = get_collision_ray_ground(&down, 4.0).unwrap();
+        assert!((hit - Vector3::new(1.0, 4.0, 5.0)).norm() < 1e-5);
+
+        let up = Ray::new(Point3::new(0.0, 10.0, 0.0), Vector3::new(0.0, 1.0, 1.0));
+        assert_eq!(get_collision_ray_ground(&up, 0.0), None);
+        let level = Ray::new(Point3::new(0.0, 10.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(get_collision_ray_ground(&level, 0.0), None);
+    }
+}
@@ -1,9 +1,10 @@
 //! Contains code related to drawing. Types that can be set as a surface to draw will implement the [`RaylibDraw`] trait
-use nalgebra::{Vector2, Vector3};
+use nalgebra::{Matrix4, Scalar, Vector2, Vector3};
 use num_traits::Float;
 use parry3d::query::Ray;
 
-use crate::core::camera::Camera3D;
+use crate::core::camera::{Camera3D, CameraLens, FRUSTUM_EDGES};
+use crate::core::math::ToFFIVector;
 
 use crate::core::texture::Texture2D;
 use crate::core::vr::RaylibVR;
@@ -243,10 +244,72 @@ impl<'a, T> RaylibDraw3D for RaylibBlendMode<'a, T> {}
 
 // Scissor Mode stuff
 
+/// A scissor rectangle in screen pixels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ScissorRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl ScissorRect {
+    /// Returns the part of this rectangle inside `bounds`, which may be empty.
+    pub fn clip(&self, bounds: &ScissorRect) -> ScissorRect {
+        let x = self.x.max(bounds.x);
+        let y = self.y.max(bounds.y);
+        let right = (self.x + self.width).min(bounds.x + bounds.width);
+        let bottom = (self.y + self.height).min(bounds.y + bounds.height);
+        ScissorRect {
+            x,
+            y,
+            width: (right - x).max(0),
+            height: (bottom - y).max(0),
+        }
+    }
+}
+
+thread_local! {
+    // Scissor rectangles currently in effect, innermost last
+    static SCISSOR_STACK: std::cell::RefCell<Vec<ScissorRect>> = std::cell::RefCell::new(Vec::new());
+}
+
+/// Clips `rect` to the active scissor rectangle (or `screen` if there is none) and makes it the active one.
+fn push_scissor(rect: ScissorRect, screen: ScissorRect) -> ScissorRect {
+    SCISSOR_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let clipped = rect.clip(stack.last().unwrap_or(&screen));
+        stack.push(clipped);
+        clipped
+    })
+}
+
+/// Removes the active scissor rectangle, returning the one it was nested in.
+fn pop_scissor() -> Option<ScissorRect> {
+    SCISSOR_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        stack.pop();
+        stack.last().copied()
+    })
+}
+
+/// Returns the scissor rectangle currently in effect, if any.
+pub fn active_scissor() -> Option<ScissorRect> {
+    SCISSOR_STACK.with(|stack| stack.borrow().last().copied())
+}
+
+/// Clips drawing to a rectangle until dropped.
+///
+/// Scissor modes can be nested. A nested rectangle is clipped to the one it is inside of, and dropping it restores the outer rectangle.
 pub struct RaylibScissorMode<'a, T>(&'a mut T);
 impl<'a, T> Drop for RaylibScissorMode<'a, T> {
     fn drop(&mut self) {
-        unsafe { ffi::EndScissorMode() }
+        match pop_scissor() {
+            Some(parent) => unsafe {
+                ffi::BeginScissorMode(parent.x, parent.y, parent.width, parent.height)
+            },
+            None => unsafe { ffi::EndScissorMode() },
+        }
     }
 }
 impl<'a, T> std::ops::Deref for RaylibScissorMode<'a, T> {
@@ -261,6 +324,7 @@ pub trait RaylibScissorModeExt
 where
     Self: Sized,
 {
+    /// Begins clipping drawing to a rectangle. Parts of the rectangle outside the screen (or the enclosing scissor rectangle) are clipped off.
     #[must_use]
     fn begin_scissor_mode(
         &mut self,
@@ -269,7 +333,24 @@ where
         width: i32,
         height: i32,
     ) -> RaylibScissorMode<Self> {
-        unsafe { ffi::BeginScissorMode(x, y, width, height) }
+        let screen = unsafe {
+            ScissorRect {
+                x: 0,
+                y: 0,
+                width: ffi::GetScreenWidth(),
+                height: ffi::GetScreenHeight(),
+            }
+        };
+        let rect = push_scissor(
+            ScissorRect {
+                x,
+                y,
+                width,
+                height,
+            },
+            screen,
+        );
+        unsafe { ffi::BeginScissorMode(rect.x, rect.y, rect.width, rect.height) }
         RaylibScissorMode(self)
     }
 }
@@ -278,6 +359,107 @@ impl<D: RaylibDraw> RaylibScissorModeExt for D {}
 impl<'a, T> RaylibDraw for RaylibScissorMode<'a, T> {}
 impl<'a, T: RaylibDraw3D> RaylibDraw3D for RaylibScissorMode<'a, T> {}
 
+// Cinematic stuff
+
+/// Black bars across the top and bottom of the screen for cutscenes, with drawing clipped to the picture between them until dropped.
+///
+/// Use [`CinematicGuard::camera_for`] as the projection so the picture keeps the full screen's horizontal framing instead of being squashed into the smaller area.
+pub struct CinematicGuard<'a, T> {
+    inner: &'a mut T,
+    bar_fraction: f32,
+    screen_aspect: f32,
+}
+
+impl<'a, T> CinematicGuard<'a, T> {
+    /// Returns the fraction of the screen height covered by the two bars together.
+    pub fn bar_fraction(&self) -> f32 {
+        self.bar_fraction
+    }
+
+    /// Returns the projection matrix for `cam` drawn between the bars, see [`Camera3D::get_projection_matrix_letterboxed`].
+    ///
+    /// Uses the default [`CameraLens`] clip planes, the same as raylib's `BeginMode3D`.
+    pub fn camera_for<U>(&self, cam: &Camera3D<U>) -> Matrix4<f32>
+    where
+        U: Float + Scalar,
+    {
+        let lens = CameraLens::default();
+        cam.get_projection_matrix_letterboxed(
+            self.screen_aspect,
+            self.bar_fraction,
+            lens.near,
+            lens.far,
+        )
+    }
+}
+
+impl<'a, T> Drop for CinematicGuard<'a, T> {
+    fn drop(&mut self) {
+        match pop_scissor() {
+            Some(parent) => unsafe {
+                ffi::BeginScissorMode(parent.x, parent.y, parent.width, parent.height)
+            },
+            None => unsafe { ffi::EndScissorMode() },
+        }
+    }
+}
+impl<'a, T> std::ops::Deref for CinematicGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+pub trait RaylibCinematicExt
+where
+    Self: Sized,
+{
+    /// Draws letterbox bars covering `bar_fraction` of the screen height in total, half at the top and half at the bottom, and clips drawing to the area between them.
+    #[must_use]
+    fn begin_cinematic(&mut self, bar_fraction: f32) -> CinematicGuard<Self> {
+        let bar_fraction = bar_fraction.max(0.0).min(0.99);
+        let (width, height) = unsafe { (ffi::GetScreenWidth(), ffi::GetScreenHeight()) };
+        let bar = (height as f32 * bar_fraction / 2.0).round() as i32;
+        let black = ffi::Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        unsafe {
+            ffi::DrawRectangle(0, 0, width, bar, black);
+            ffi::DrawRectangle(0, height - bar, width, bar, black);
+        }
+
+        let screen = ScissorRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let rect = push_scissor(
+            ScissorRect {
+                x: 0,
+                y: bar,
+                width,
+                height: height - 2 * bar,
+            },
+            screen,
+        );
+        unsafe { ffi::BeginScissorMode(rect.x, rect.y, rect.width, rect.height) }
+        CinematicGuard {
+            inner: self,
+            bar_fraction,
+            screen_aspect: width as f32 / height.max(1) as f32,
+        }
+    }
+}
+
+impl<D: RaylibDraw> RaylibCinematicExt for D {}
+impl<'a, T> RaylibDraw for CinematicGuard<'a, T> {}
+impl<'a, T: RaylibDraw3D> RaylibDraw3D for CinematicGuard<'a, T> {}
+
 // Actual drawing functions
 
 pub trait RaylibDraw {
@@ -1171,6 +1353,32 @@ pub trait RaylibDraw3D {
         }
     }
 
+    /// Draws the wireframe of a camera's view frustum.
+    ///
+    /// This is for visualizing a camera other than the one being drawn with, so call it inside the 3D mode of a different camera.
+    fn draw_camera_frustum<T>(
+        &mut self,
+        camera: &Camera3D<T>,
+        aspect: f32,
+        near: f32,
+        far: f32,
+        color: impl Into<ffi::Color>,
+    ) where
+        T: Float + Scalar,
+    {
+        let color = color.into();
+        let corners = camera.frustum_corners(aspect, near, far);
+        for &(a, b) in FRUSTUM_EDGES.iter() {
+            unsafe {
+                ffi::DrawLine3D(
+                    corners[a].to_ffi_vector(),
+                    corners[b].to_ffi_vector(),
+                    color,
+                );
+            }
+        }
+    }
+
     /// Draws a circle in 3D world space.
     #[inline]
     #[allow(non_snake_case)]
@@ -1511,3 +1719,92 @@ pub trait RaylibDraw3D {
         }
     }
 }
+
+#[cfg(test)]
+mod test_drawing {
+    use super::*;
+
+    const SCREEN: ScissorRect = ScissorRect {
+        x: 0,
+        y: 0,
+        width: 640,
+        height: 480,
+    };
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> ScissorRect {
+        ScissorRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_scissor_clamps_to_screen() {
+        assert_eq!(
+            push_scissor(rect(-10, -20, 100, 100), SCREEN),
+            rect(0, 0, 90, 80)
+        );
+        pop_scissor();
+        assert_eq!(
+            push_scissor(rect(600, 400, 100, 100), SCREEN),
+            rect(600, 400, 40, 80)
+        );
+        pop_scissor();
+        assert_eq!(active_scissor(), None);
+    }
+
+    #[test]
+    fn test_scissor_nesting_restores_parent() {
+        let parent = push_scissor(rect(100, 100, 200, 200), SCREEN);
+        let child = push_scissor(rect(250, 50, 100, 100), SCREEN);
+        assert_eq!(child, rect(250, 100, 50, 50));
+        assert_eq!(active_scissor(), Some(child));
+        assert_eq!(pop_scissor(), Some(parent));
+        assert_eq!(pop_scissor(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_scissor_mode_window() {
+        crate::core::test_harness::with_test_window(|rl, thread| {
+            let mut d = rl.begin_drawing(thread);
+            {
+                let mut outer = d.begin_scissor_mode(-10, 10, 100, 100);
+                {
+                    let _inner = outer.begin_scissor_mode(50, 50, 100, 100);
+                    assert_eq!(active_scissor(), Some(rect(50, 50, 40, 60)));
+                }
+                assert_eq!(active_scissor(), Some(rect(0, 10, 90, 100)));
+            }
+            assert_eq!(active_scissor(), None);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_cinematic_sets_and_clears_scissor() {
+        crate::core::test_harness::with_test_window(|rl, thread| {
+            let camera = Camera3D::perspective(
+                Vector3::new(0.0f32, 1.0, 5.0),
+                Vector3::zeros(),
+                Vector3::y(),
+                60.0,
+            );
+            let mut d = rl.begin_drawing(thread);
+            {
+                let cinematic = d.begin_cinematic(0.25);
+                assert_eq!(active_scissor(), Some(rect(0, 30, 320, 180)));
+                let expected = camera.get_projection_matrix_letterboxed(
+                    320.0 / 240.0,
+                    0.25,
+                    CameraLens::default().near,
+                    CameraLens::default().far,
+                );
+                assert_eq!(cinematic.camera_for(&camera), expected);
+            }
+            assert_eq!(active_scissor(), None);
+        });
+    }
+}
@@ -1,7 +1,8 @@
 //! Keyboard, Controller, and Mouse related functions
-use nalgebra::Vector2;
+use nalgebra::{Scalar, Vector2};
 
-use crate::consts::GestureType;
+use crate::consts::{Gesture, GestureType};
+use crate::core::math::FromFFIVector;
 use crate::core::RaylibHandle;
 use crate::ffi;
 
@@ -52,6 +53,18 @@ impl RaylibHandle {
         None
     }
 
+    /// Gets the next character typed, for text entry. Each call takes one character off raylib's queue, `None` once it's empty.
+    ///
+    /// Unlike key codes this follows the keyboard layout and shift state.
+    #[inline]
+    pub fn get_char_pressed(&self) -> Option<char> {
+        let c = unsafe { ffi::GetCharPressed() };
+        if c > 0 {
+            return std::char::from_u32(c as u32);
+        }
+        None
+    }
+
     /// Sets a custom key to exit program (default is ESC).
     // #[inline]
     pub fn set_exit_key(&mut self, key: Option<crate::consts::KeyboardKey>) {
@@ -196,14 +209,22 @@ impl RaylibHandle {
         Vector2::from_ffi_vector(unsafe { ffi::GetMousePosition() })
     }
 
+    /// Returns how far the mouse moved since the last frame.
+    ///
+    /// `GetMouseDelta` was added in raylib 4.0, so this needs the `raylib-4-0` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-0")]
+    pub fn get_mouse_delta(&self) -> Vector2<f32> {
+        Vector2::from_ffi_vector(unsafe { ffi::GetMouseDelta() })
+    }
+
     /// Sets mouse position.
+    ///
+    /// raylib treats this as a jump rather than movement, so it doesn't show up in the next mouse delta. That makes it safe for recentering the cursor during mouse-look.
     #[inline]
-    pub fn set_mouse_position<T>(&mut self, position: Vector2<T>)
-    where
-        T: Into<i32>,
-    {
+    pub fn set_mouse_position(&mut self, x: i32, y: i32) {
         unsafe {
-            ffi::SetMousePosition(position.x as i32, position.y as i32);
+            ffi::SetMousePosition(x, y);
         }
     }
 
@@ -211,10 +232,10 @@ impl RaylibHandle {
     #[inline]
     pub fn set_mouse_offset<T>(&mut self, offset: Vector2<T>)
     where
-        T: Into<i32>,
+        T: Into<i32> + Scalar + Copy,
     {
         unsafe {
-            ffi::SetMouseOffset(offset.x as i32, offset.y as i32);
+            ffi::SetMouseOffset(offset.x.into(), offset.y.into());
         }
     }
 
@@ -266,8 +287,8 @@ impl RaylibHandle {
 
     /// Gets latest detected gesture.
     #[inline]
-    pub fn get_gesture_detected(&self) -> GestureType {
-        unsafe { std::mem::transmute(ffi::GetGestureDetected()) }
+    pub fn get_gesture_detected(&self) -> Gesture {
+        Gesture::from_flags(unsafe { ffi::GetGestureDetected() })
     }
 
     /// Gets touch points count.
@@ -418,3 +439,41 @@ pub fn key_from_i32(key: i32) -> Option<crate::consts::KeyboardKey> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod test_input {
+    use super::*;
+    use crate::consts::KeyboardKey::*;
+
+    #[test]
+    fn test_key_codes_round_trip() {
+        for &key in &[KEY_A, KEY_W, KEY_SPACE, KEY_LEFT_CONTROL, KEY_F12, KEY_KP_9] {
+            assert_eq!(key_from_i32(key as i32), Some(key));
+        }
+        assert_eq!(key_from_i32(0), None);
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_key_polling_smoke() {
+        use crate::core::camera::CameraInput;
+
+        crate::core::test_harness::with_test_window(|rl, _| {
+            // Nobody is at the keyboard of a hidden window
+            assert!(rl.is_key_up(KEY_A) && !rl.is_key_down(KEY_A));
+            assert!(!rl.is_key_pressed(KEY_A) && !rl.is_key_released(KEY_A));
+            assert_eq!(rl.get_key_pressed(), None);
+            assert_eq!(rl.key_up(KEY_A), rl.is_key_up(KEY_A));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_set_mouse_position() {
+        crate::core::test_harness::with_test_window(|rl, _| {
+            rl.set_mouse_position(100, 60);
+            assert_eq!((rl.get_mouse_x(), rl.get_mouse_y()), (100, 60));
+            assert_eq!(rl.get_mouse_position(), Vector2::new(100.0, 60.0));
+        });
+    }
+}
@@ -1,4 +1,5 @@
-use nalgebra::{Vector2, Vector3};
+use nalgebra::{Matrix4, Scalar, Vector2, Vector3};
+use num_traits::Float;
 
 use crate::ffi;
 
@@ -14,7 +15,7 @@ impl FromFFIVector<ffi::Vector2> for Vector2<f32> {
 
 impl FromFFIVector<ffi::Vector3> for Vector3<f32> {
     fn from_ffi_vector(v: ffi::Vector3) -> Self {
-        Vector2::new(v.x, v.y, v.z)
+        Vector3::new(v.x, v.y, v.z)
     }
 }
 
@@ -45,25 +46,26 @@ pub trait ToFFIMatrix<T> {
     fn to_ffi_matrix(&self) -> T;
 }
 
+// raylib names matrix fields by their column-major index, so `m4` is row 0, column 1.
 impl ToFFIMatrix<ffi::Matrix> for nalgebra::Matrix4<f32> {
     fn to_ffi_matrix(&self) -> ffi::Matrix {
         ffi::Matrix {
-            m0: self.m00,
-            m1: self.m01,
-            m2: self.m02,
-            m3: self.m03,
-            m10: self.m10,
-            m11: self.m11,
-            m12: self.m12,
-            m13: self.m13,
-            m20: self.m20,
-            m21: self.m21,
-            m22: self.m22,
-            m23: self.m23,
-            m30: self.m30,
-            m31: self.m31,
-            m32: self.m32,
-            m33: self.m33,
+            m0: self.m11,
+            m4: self.m12,
+            m8: self.m13,
+            m12: self.m14,
+            m1: self.m21,
+            m5: self.m22,
+            m9: self.m23,
+            m13: self.m24,
+            m2: self.m31,
+            m6: self.m32,
+            m10: self.m33,
+            m14: self.m34,
+            m3: self.m41,
+            m7: self.m42,
+            m11: self.m43,
+            m15: self.m44,
         }
     }
 }
@@ -75,12 +77,246 @@ pub trait FromFFIMatrix<T> {
 impl FromFFIMatrix<ffi::Matrix> for nalgebra::Matrix4<f32> {
     fn from_ffi_matrix(m: ffi::Matrix) -> Self {
         nalgebra::Matrix4::new(
-            m.m0, m.m1, m.m2, m.m3, m.m10, m.m11, m.m12, m.m13, m.m20, m.m21, m.m22, m.m23,
-            m.m30, m.m31, m.m32, m.m33,
+            m.m0, m.m4, m.m8, m.m12, m.m1, m.m5, m.m9, m.m13, m.m2, m.m6, m.m10, m.m14, m.m3, m.m7,
+            m.m11, m.m15,
         )
     }
 }
 
+/// Returns the identity matrix.
+#[inline]
+pub fn matrix_identity() -> ffi::Matrix {
+    Matrix4::identity().to_ffi_matrix()
+}
+
+/// Multiplies two matrices as `left * right`, so `right` is applied to a vector first.
+#[inline]
+pub fn matrix_multiply(left: ffi::Matrix, right: ffi::Matrix) -> ffi::Matrix {
+    (Matrix4::from_ffi_matrix(left) * Matrix4::from_ffi_matrix(right)).to_ffi_matrix()
+}
+
+/// Inverts a matrix. Returns `None` if it is singular.
+#[inline]
+pub fn matrix_invert(m: ffi::Matrix) -> Option<ffi::Matrix> {
+    Matrix4::from_ffi_matrix(m)
+        .try_inverse()
+        .map(|inv| inv.to_ffi_matrix())
+}
+
+/// Transposes a matrix.
+#[inline]
+pub fn matrix_transpose(m: ffi::Matrix) -> ffi::Matrix {
+    Matrix4::from_ffi_matrix(m).transpose().to_ffi_matrix()
+}
+
+/// Converts an angle in degrees to radians.
+#[inline]
+pub fn deg_to_rad<T: Float>(degrees: T) -> T {
+    degrees.to_radians()
+}
+
+/// Converts an angle in radians to degrees.
+#[inline]
+pub fn rad_to_deg<T: Float>(radians: T) -> T {
+    radians.to_degrees()
+}
+
+/// Converts spherical coordinates to a cartesian offset from the origin.
+///
+/// Angles are in radians. `azimuth` turns around the Y axis, 0 pointing along +Z and π/2 along +X. `elevation` is the angle above the XZ plane, π/2 pointing straight up.
+pub fn spherical_to_cartesian<T>(azimuth: T, elevation: T, radius: T) -> Vector3<T>
+where
+    T: Float + Scalar,
+{
+    Vector3::new(
+        elevation.cos() * azimuth.sin(),
+        elevation.sin(),
+        elevation.cos() * azimuth.cos(),
+    ) * radius
+}
+
+/// Converts a cartesian offset to `(azimuth, elevation, radius)`, the inverse of [`spherical_to_cartesian`].
+///
+/// Azimuth is in `(-π, π]`. On the Y axis the azimuth is 0, and at the origin every component is 0.
+pub fn cartesian_to_spherical<T>(v: Vector3<T>) -> (T, T, T)
+where
+    T: Float + Scalar,
+{
+    let radius = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if radius == T::zero() {
+        return (T::zero(), T::zero(), T::zero());
+    }
+    let azimuth = v.x.atan2(v.z);
+    let elevation = (v.y / radius).max(-T::one()).min(T::one()).asin();
+    (azimuth, elevation, radius)
+}
+
+/// Spherically interpolates between the unit vectors `a` and `b`, turning at a constant rate and keeping the result unit length.
+///
+/// Use this rather than a plain lerp for directions like a camera's up or forward vector, which a lerp shortens on the way.
+/// Nearly parallel inputs fall back to a normalized lerp, and for opposite inputs the turn goes around an arbitrary perpendicular axis.
+pub fn slerp_unit<T>(a: Vector3<T>, b: Vector3<T>, t: T) -> Vector3<T>
+where
+    T: Float + Scalar,
+{
+    let dot = a.dot(&b).max(-T::one()).min(T::one());
+    if dot > T::one() - T::from(1e-4).unwrap() {
+        let lerped = a + (b - a) * t;
+        let length = lerped.dot(&lerped).sqrt();
+        return if length > T::epsilon() {
+            lerped / length
+        } else {
+            a
+        };
+    }
+
+    // Unit vector perpendicular to `a` in the plane of the turn
+    let mut perpendicular = b - a * dot;
+    if perpendicular.dot(&perpendicular) < T::from(1e-8).unwrap() {
+        let axis = if a.x.abs() < T::from(0.9).unwrap() {
+            Vector3::new(T::one(), T::zero(), T::zero())
+        } else {
+            Vector3::new(T::zero(), T::one(), T::zero())
+        };
+        perpendicular = a.cross(&axis);
+    }
+    let perpendicular = perpendicular / perpendicular.dot(&perpendicular).sqrt();
+
+    let angle = dot.acos() * t;
+    a * angle.cos() + perpendicular * angle.sin()
+}
+
+/// An angle in degrees, for making the unit explicit where it matters, like a camera's `fovy`.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct Degrees(pub f32);
+
+/// An angle in radians, see [`Degrees`].
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct Radians(pub f32);
+
+impl From<Radians> for Degrees {
+    fn from(r: Radians) -> Degrees {
+        Degrees(rad_to_deg(r.0))
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(d: Degrees) -> Radians {
+        Radians(deg_to_rad(d.0))
+    }
+}
+
+/// A rectangle of the screen that gets rendered to, in pixels with `(0, 0)` at the top left.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Viewport {
+    /// Creates a viewport with its top left corner at `(x, y)`.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Viewport {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a viewport covering a whole screen of the given size.
+    pub fn from_size(size: Vector2<f32>) -> Self {
+        Viewport::new(0.0, 0.0, size.x, size.y)
+    }
+
+    /// Returns the width divided by the height.
+    pub fn aspect(&self) -> f32 {
+        self.width / self.height
+    }
+}
+
+/// Converts normalized device coordinates to a screen position inside `viewport`.
+///
+/// NDC runs from -1 to 1 with Y pointing up, while screen Y points down, so NDC `(-1, -1)` is the bottom left corner of the viewport.
+pub fn ndc_to_screen(ndc: Vector2<f32>, viewport: Viewport) -> Vector2<f32> {
+    Vector2::new(
+        viewport.x + (ndc.x + 1.0) / 2.0 * viewport.width,
+        viewport.y + (1.0 - ndc.y) / 2.0 * viewport.height,
+    )
+}
+
+/// Converts a screen position to normalized device coordinates for `viewport`, the inverse of [`ndc_to_screen`].
+pub fn screen_to_ndc(screen: Vector2<f32>, viewport: Viewport) -> Vector2<f32> {
+    Vector2::new(
+        (screen.x - viewport.x) / viewport.width * 2.0 - 1.0,
+        1.0 - (screen.y - viewport.y) / viewport.height * 2.0,
+    )
+}
+
+/// Narrows a vector to the `f32` precision raylib works in.
+pub(crate) fn vec3_to_f32<T>(v: &Vector3<T>) -> Vector3<f32>
+where
+    T: Float + Scalar,
+{
+    Vector3::new(
+        v.x.to_f32().unwrap(),
+        v.y.to_f32().unwrap(),
+        v.z.to_f32().unwrap(),
+    )
+}
+
+/// Widens an `f32` vector back to the caller's precision.
+pub(crate) fn vec3_from_f32<T>(v: &Vector3<f32>) -> Vector3<T>
+where
+    T: Float + Scalar,
+{
+    Vector3::new(
+        T::from(v.x).unwrap(),
+        T::from(v.y).unwrap(),
+        T::from(v.z).unwrap(),
+    )
+}
+
+/// Narrows a 2D vector to `f32`, see [`vec3_to_f32`].
+pub(crate) fn vec2_to_f32<T>(v: &Vector2<T>) -> Vector2<f32>
+where
+    T: Float + Scalar,
+{
+    Vector2::new(v.x.to_f32().unwrap(), v.y.to_f32().unwrap())
+}
+
+/// Widens an `f32` 2D vector back to the caller's precision.
+pub(crate) fn vec2_from_f32<T>(v: &Vector2<f32>) -> Vector2<T>
+where
+    T: Float + Scalar,
+{
+    Vector2::new(T::from(v.x).unwrap(), T::from(v.y).unwrap())
+}
+
+/// The steepest pitch in degrees the camera helpers allow. Any closer to straight up or down and the view can flip over.
+pub(crate) const MAX_CAMERA_PITCH: f32 = 89.0;
+
+/// Clamps a pitch in degrees to `[min_deg, max_deg]`, and never past [`MAX_CAMERA_PITCH`] either way.
+pub(crate) fn clamp_pitch(pitch: f32, min_deg: f32, max_deg: f32) -> f32 {
+    pitch
+        .max(min_deg.max(-MAX_CAMERA_PITCH))
+        .min(max_deg.min(MAX_CAMERA_PITCH))
+}
+
+/// Wraps an angle in degrees into `(-180, 180]`.
+pub(crate) fn wrap_degrees(angle: f32) -> f32 {
+    let a = angle % 360.0;
+    if a > 180.0 {
+        a - 360.0
+    } else if a <= -180.0 {
+        a + 360.0
+    } else {
+        a
+    }
+}
+
 // use cgmath::Vector2;
 // use num_traits::Float;
 
@@ -1758,3 +1994,101 @@ impl FromFFIMatrix<ffi::Matrix> for nalgebra::Matrix4<f32> {
 //         )
 //     }
 // }
+
+#[cfg(test)]
+mod test_math {
+    use super::*;
+    use crate::core::camera::Camera3D;
+
+    fn assert_matrix_eq(a: ffi::Matrix, b: ffi::Matrix) {
+        let diff = Matrix4::from_ffi_matrix(a) - Matrix4::from_ffi_matrix(b);
+        assert!(diff.iter().all(|x| x.abs() < 1e-5), "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_deg_rad_round_trip() {
+        assert!((deg_to_rad(90.0f32) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((rad_to_deg(deg_to_rad(90.0f32)) - 90.0).abs() < 1e-4);
+        assert!((rad_to_deg(deg_to_rad(90.0f64)) - 90.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_layout() {
+        let m = Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0)).to_ffi_matrix();
+        assert_eq!((m.m12, m.m13, m.m14, m.m15), (1.0, 2.0, 3.0, 1.0));
+        assert_matrix_eq(Matrix4::from_ffi_matrix(m).to_ffi_matrix(), m);
+    }
+
+    #[test]
+    fn test_matrix_invert() {
+        let camera = Camera3D::perspective(
+            Vector3::new(3.0f32, 4.0, 5.0),
+            Vector3::zeros(),
+            Vector3::y(),
+            60.0,
+        );
+        let view = camera.get_view_matrix().to_ffi_matrix();
+        let inv = matrix_invert(view).unwrap();
+        assert_matrix_eq(matrix_multiply(inv, view), matrix_identity());
+        assert_matrix_eq(matrix_transpose(matrix_transpose(view)), view);
+    }
+
+    #[test]
+    fn test_spherical_round_trip() {
+        for a in -17..=18 {
+            // Stop short of the poles, where azimuth is undefined
+            for e in -8..=8 {
+                let azimuth = deg_to_rad(a as f64 * 10.0);
+                let elevation = deg_to_rad(e as f64 * 10.0);
+                let v = spherical_to_cartesian(azimuth, elevation, 2.5);
+                assert!((v.norm() - 2.5).abs() < 1e-9);
+                let (az, el, r) = cartesian_to_spherical(v);
+                assert!((az - azimuth).abs() < 1e-9, "{} != {}", az, azimuth);
+                assert!((el - elevation).abs() < 1e-9);
+                assert!((r - 2.5).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slerp_unit() {
+        let half = slerp_unit(Vector3::x(), Vector3::y(), 0.5f32);
+        let expected = Vector3::new(1.0, 1.0, 0.0).normalize();
+        assert!((half - expected).norm() < 1e-6);
+        assert!((slerp_unit(Vector3::x(), Vector3::y(), 1.0f32) - Vector3::y()).norm() < 1e-6);
+
+        let turned = slerp_unit(Vector3::y(), -Vector3::y(), 0.5f32);
+        assert!(turned.iter().all(|c| c.is_finite()));
+        assert!((turned.norm() - 1.0).abs() < 1e-6 && turned.y.abs() < 1e-6);
+
+        let close = Vector3::new(1.0, 1e-5, 0.0).normalize();
+        let nearly = slerp_unit(Vector3::x(), close, 0.5f32);
+        assert!((nearly.norm() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spherical_axes() {
+        let east = spherical_to_cartesian(deg_to_rad(90.0f32), 0.0, 1.0);
+        assert!((east - Vector3::x()).norm() < 1e-6);
+        let up = spherical_to_cartesian(0.0f32, deg_to_rad(90.0), 1.0);
+        assert!((up - Vector3::y()).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_ndc_screen_conventions() {
+        let viewport = Viewport::new(100.0, 50.0, 400.0, 300.0);
+        let bottom_left = ndc_to_screen(Vector2::new(-1.0, -1.0), viewport);
+        assert_eq!(bottom_left, Vector2::new(100.0, 350.0));
+        let top_right = ndc_to_screen(Vector2::new(1.0, 1.0), viewport);
+        assert_eq!(top_right, Vector2::new(500.0, 50.0));
+
+        for &ndc in &[
+            Vector2::new(0.25, -0.5),
+            Vector2::new(-0.9, 0.7),
+            Vector2::zeros(),
+        ] {
+            let back = screen_to_ndc(ndc_to_screen(ndc, viewport), viewport);
+            assert!((back - ndc).norm() < 1e-6);
+        }
+    }
+}
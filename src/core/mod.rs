@@ -19,6 +19,9 @@ pub mod texture;
 pub mod vr;
 pub mod window;
 
+#[cfg(all(test, feature = "integration-tests"))]
+pub(crate) mod test_harness;
+
 use crate::ffi;
 use std::ffi::CString;
 use std::marker::PhantomData;
@@ -61,11 +64,11 @@ pub struct RaylibHandle(()); // inner field is private, preventing manual constr
 
 impl Drop for RaylibHandle {
     fn drop(&mut self) {
-        if IS_INITIALIZED.load(Ordering::Relaxed) {
+        if IS_INITIALIZED.load(Ordering::SeqCst) {
             unsafe {
                 ffi::CloseWindow();
             }
-            IS_INITIALIZED.store(false, Ordering::Relaxed);
+            IS_INITIALIZED.store(false, Ordering::SeqCst);
         }
     }
 }
@@ -77,6 +80,7 @@ pub struct RaylibBuilder {
     window_resizable: bool,
     window_undecorated: bool,
     window_transparent: bool,
+    window_hidden: bool,
     msaa_4x_hint: bool,
     vsync_hint: bool,
     width: i32,
@@ -119,6 +123,12 @@ impl RaylibBuilder {
         self
     }
 
+    /// Sets the window to be hidden.
+    pub fn hidden(&mut self) -> &mut Self {
+        self.window_hidden = true;
+        self
+    }
+
     /// Hints that 4x MSAA (anti-aliasing) should be enabled. The system's graphics drivers may override this setting.
     pub fn msaa_4x(&mut self) -> &mut Self {
         self.msaa_4x_hint = true;
@@ -176,6 +186,9 @@ impl RaylibBuilder {
         if self.window_transparent {
             flags |= FLAG_WINDOW_TRANSPARENT as u32;
         }
+        if self.window_hidden {
+            flags |= FLAG_WINDOW_HIDDEN as u32;
+        }
         if self.msaa_4x_hint {
             flags |= FLAG_MSAA_4X_HINT as u32;
         }
@@ -186,28 +199,70 @@ impl RaylibBuilder {
         unsafe {
             ffi::SetConfigFlags(flags as u32);
         }
-        let rl = init_window(self.width, self.height, &self.title);
-        (rl, RaylibThread(PhantomData))
+        init_window(self.width, self.height, &self.title)
     }
 }
 
 /// Initializes window and OpenGL context.
 ///
+/// Dropping the returned `RaylibHandle` closes the window again, after which a new one can be created. Use [`init`] instead to set window options first.
+///
 /// # Panics
 ///
-/// Attempting to initialize Raylib more than once will result in a panic.
-fn init_window(width: i32, height: i32, title: &str) -> RaylibHandle {
-    if IS_INITIALIZED.load(Ordering::Relaxed) {
+/// Attempting to initialize Raylib while a `RaylibHandle` is still alive will result in a panic.
+pub fn init_window(width: i32, height: i32, title: &str) -> (RaylibHandle, RaylibThread) {
+    // Anything that can fail has to happen before the flag is claimed, nothing would clear it afterwards
+    let c_title = CString::new(title).unwrap();
+    // Claim the flag before touching raylib, so two threads can't both get past this
+    if IS_INITIALIZED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
         panic!("Attempted to initialize raylib-rs more than once!");
-    } else {
-        unsafe {
-            let c_title = CString::new(title).unwrap();
-            ffi::InitWindow(width, height, c_title.as_ptr());
-        }
-        if !unsafe { ffi::IsWindowReady() } {
-            panic!("Attempting to create window failed!");
-        }
-        IS_INITIALIZED.store(true, Ordering::Relaxed);
-        RaylibHandle(())
+    }
+    unsafe {
+        ffi::InitWindow(width, height, c_title.as_ptr());
+    }
+    if !unsafe { ffi::IsWindowReady() } {
+        IS_INITIALIZED.store(false, Ordering::SeqCst);
+        panic!("Attempting to create window failed!");
+    }
+    (RaylibHandle(()), RaylibThread(PhantomData))
+}
+
+#[cfg(test)]
+mod test_core {
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_window_reopens_after_drop() {
+        use crate::core::test_harness::with_test_window;
+
+        let first = with_test_window(|rl, _| rl.get_screen_width());
+        let second = with_test_window(|rl, _| rl.get_screen_width());
+        assert_eq!((first, second), (320, 320));
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_second_init_is_rejected() {
+        crate::core::test_harness::with_test_window(|rl, _| {
+            let second = std::panic::catch_unwind(|| super::init_window(100, 100, "second"));
+            assert!(second.is_err());
+            // The first window is untouched
+            assert_eq!(rl.get_screen_width(), 320);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_bad_title_leaves_init_available() {
+        use crate::core::test_harness::{lock_window, with_test_window};
+
+        let bad = {
+            let _guard = lock_window();
+            std::panic::catch_unwind(|| super::init_window(100, 100, "bad\0title"))
+        };
+        assert!(bad.is_err());
+        assert_eq!(with_test_window(|rl, _| rl.get_screen_width()), 320);
     }
 }
@@ -1,7 +1,9 @@
 //! Code for the safe manipulation of shaders
 use crate::consts::ShaderUniformDataType;
+use crate::core::math::FromFFIMatrix;
 use crate::core::{RaylibHandle, RaylibThread};
 use crate::ffi;
+use nalgebra::Matrix4;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_void};
 
@@ -312,14 +314,46 @@ impl RaylibHandle {
     }
 
     /// Gets internal modelview matrix.
+    ///
+    /// Call this inside `begin_mode3D` to read back the view raylib actually set up for the camera.
     #[inline]
-    pub fn get_matrix_modelview(&self) -> Matrix {
-        unsafe { ffi::GetMatrixModelview().into() }
+    pub fn get_matrix_modelview(&self) -> Matrix4<f32> {
+        Matrix4::from_ffi_matrix(unsafe { ffi::GetMatrixModelview() })
     }
 
     /// Gets internal projection matrix.
+    ///
+    /// Like [`RaylibHandle::get_matrix_modelview`], this is only meaningful inside `begin_mode3D`.
     #[inline]
-    pub fn get_matrix_projection(&self) -> Matrix {
-        unsafe { ffi::GetMatrixProjection().into() }
+    pub fn get_matrix_projection(&self) -> Matrix4<f32> {
+        Matrix4::from_ffi_matrix(unsafe { ffi::GetMatrixProjection() })
+    }
+}
+
+#[cfg(test)]
+mod test_shaders {
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_matrix_readback_matches_camera() {
+        use crate::core::camera::Camera3D;
+        use crate::core::drawing::RaylibMode3DExt;
+        use nalgebra::Vector3;
+
+        crate::core::test_harness::with_test_window(|rl, thread| {
+            let camera = Camera3D::perspective(
+                Vector3::new(4.0f32, 3.0, 5.0),
+                Vector3::new(0.0, 0.5, 0.0),
+                Vector3::y(),
+                60.0,
+            );
+            let aspect = rl.get_screen_width() as f32 / rl.get_screen_height() as f32;
+            let view = camera.get_view_matrix();
+            let proj = camera.get_projection_matrix_default(aspect);
+
+            let mut d = rl.begin_drawing(thread);
+            let m3 = d.begin_mode3D(&camera);
+            assert!((m3.get_matrix_modelview() - view).amax() < 1e-4);
+            assert!((m3.get_matrix_projection() - proj).amax() < 1e-4);
+        });
     }
 }
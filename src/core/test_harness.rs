@@ -0,0 +1,32 @@
+//! Support for tests that need a real raylib window
+//!
+//! These only build with the `integration-tests` feature, run them with `cargo test --features integration-tests`.
+use std::sync::{Mutex, MutexGuard};
+
+use crate::core::{RaylibHandle, RaylibThread};
+
+lazy_static::lazy_static! {
+    /// raylib only supports one window per process, so tests take turns.
+    static ref WINDOW_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Waits for any other test's window to close, for tests that call the init functions themselves.
+pub(crate) fn lock_window() -> MutexGuard<'static, ()> {
+    // A test that panicked while holding the lock still closed its window
+    WINDOW_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Runs `f` with a small hidden window open, closing it afterwards even if `f` panics.
+pub(crate) fn with_test_window<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut RaylibHandle, &RaylibThread) -> R,
+{
+    let _guard = lock_window();
+    let (mut rl, thread) = crate::init()
+        .size(320, 240)
+        .title("raylib-eco integration test")
+        .hidden()
+        .build();
+    // Dropping `rl`, including while unwinding, closes the window
+    f(&mut rl, &thread)
+}
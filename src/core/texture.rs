@@ -53,6 +53,8 @@ make_thin_wrapper!(
     ffi::UnloadRenderTexture
 );
 make_thin_wrapper!(WeakRenderTexture2D, ffi::RenderTexture2D, no_drop);
+/// raylib's `RenderTexture` is another name for `RenderTexture2D`.
+pub type RenderTexture = RenderTexture2D;
 
 // Weak things can be clone
 impl Clone for WeakTexture2D {
@@ -984,3 +986,19 @@ impl RaylibHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod test_texture {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_load_render_texture_size() {
+        crate::core::test_harness::with_test_window(|rl, thread| {
+            let target = rl.load_render_texture(thread, 64, 64).unwrap();
+            assert_ne!(target.id(), 0);
+            // width() and height() come from the color attachment
+            assert_eq!((target.width(), target.height()), (64, 64));
+        });
+    }
+}
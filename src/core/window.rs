@@ -1,4 +1,11 @@
 //! Window manipulation functions
+use nalgebra::{Point3, Scalar, Vector3};
+use num_traits::Float;
+use parry3d::query::Ray;
+
+use crate::core::camera::Camera3D;
+use crate::core::collision::{get_collision_ray_ground, pick_nearest_box, BoundingBox};
+use crate::core::math::{vec3_from_f32, ToFFIVector};
 use crate::core::{RaylibHandle, RaylibThread};
 use crate::ffi;
 use std::ffi::{CStr, CString, IntoStringError, NulError};
@@ -389,7 +396,48 @@ impl RaylibHandle {
         mouse_position: impl Into<ffi::Vector2>,
         camera: impl Into<ffi::Camera>,
     ) -> Ray {
-        unsafe { ffi::GetMouseRay(mouse_position.into(), camera.into()).into() }
+        let ray = unsafe { ffi::GetMouseRay(mouse_position.into(), camera.into()) };
+        Ray::new(
+            Point3::new(ray.position.x, ray.position.y, ray.position.z),
+            Vector3::new(ray.direction.x, ray.direction.y, ray.direction.z),
+        )
+    }
+
+    /// Returns where the mouse points on the horizontal plane at height `plane_y`, see [`get_collision_ray_ground`].
+    pub fn mouse_ground_position<T>(&self, camera: &Camera3D<T>, plane_y: T) -> Option<Vector3<T>>
+    where
+        T: Float + Scalar,
+    {
+        self.screen_ground_position(self.get_mouse_position(), camera, plane_y)
+    }
+
+    /// Returns where a screen position points on the horizontal plane at height `plane_y`, for touch input and other pointers.
+    pub fn screen_ground_position<T>(
+        &self,
+        screen_position: nalgebra::Vector2<f32>,
+        camera: &Camera3D<T>,
+        plane_y: T,
+    ) -> Option<Vector3<T>>
+    where
+        T: Float + Scalar,
+    {
+        let ray = self.get_mouse_ray(screen_position.to_ffi_vector(), camera);
+        get_collision_ray_ground(&ray, plane_y.to_f32().unwrap()).map(|p| vec3_from_f32(&p))
+    }
+
+    /// Returns the index of the nearest box under the mouse, see [`pick_nearest_box`].
+    pub fn pick_nearest<T>(
+        &self,
+        mouse: nalgebra::Vector2<T>,
+        camera: &Camera3D<T>,
+        boxes: &[BoundingBox<T>],
+    ) -> Option<usize>
+    where
+        T: Float + Scalar,
+    {
+        let mouse = nalgebra::Vector2::new(mouse.x.to_f32().unwrap(), mouse.y.to_f32().unwrap());
+        let ray = self.get_mouse_ray(mouse.to_ffi_vector(), camera);
+        pick_nearest_box(&ray, boxes)
     }
 
     /// Returns the screen space position for a 3d world space position
@@ -652,6 +700,35 @@ impl RaylibHandle {
         unsafe { ffi::GetScreenHeight() }
     }
 
+    /// Gets the width of the framebuffer being drawn to, in pixels.
+    ///
+    /// On high-DPI displays this is the screen width times the DPI scale. Mouse positions and window sizes are in screen units, use the render size for anything measured in real pixels, like shader resolutions and projection aspect ratios.
+    ///
+    /// `GetRenderWidth` was added in raylib 4.1, so this needs the `raylib-4-1` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-1")]
+    pub fn get_render_width(&self) -> i32 {
+        unsafe { ffi::GetRenderWidth() }
+    }
+
+    /// Gets the height of the framebuffer being drawn to, in pixels. See [`RaylibHandle::get_render_width`].
+    ///
+    /// Needs raylib 4.1 and the `raylib-4-1` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-1")]
+    pub fn get_render_height(&self) -> i32 {
+        unsafe { ffi::GetRenderHeight() }
+    }
+
+    /// Returns the aspect ratio of the framebuffer, the one `BeginMode3D` builds its projection with.
+    ///
+    /// Needs raylib 4.1 and the `raylib-4-1` feature.
+    #[inline]
+    #[cfg(feature = "raylib-4-1")]
+    pub fn get_render_aspect(&self) -> f32 {
+        self.get_render_width() as f32 / self.get_render_height().max(1) as f32
+    }
+
     /// Get window position
     #[inline]
     pub fn get_window_position(&self) -> Vector2 {
@@ -705,3 +782,69 @@ impl RaylibHandle {
         ffi::GetWindowHandle()
     }
 }
+
+#[cfg(test)]
+mod test_window {
+    #[test]
+    #[cfg(feature = "integration-tests")]
+    fn test_screen_ground_position() {
+        use crate::core::camera::Camera3D;
+        use nalgebra::{Vector2, Vector3};
+
+        crate::core::test_harness::with_test_window(|rl, _| {
+            // Looking down at 45 degrees from 10 units up
+            let camera = Camera3D::perspective(
+                Vector3::new(0.0f32, 10.0, 0.0),
+                Vector3::new(0.0, 0.0, 10.0),
+                Vector3::y(),
+                60.0,
+            );
+            let center = Vector2::new(
+                rl.get_screen_width() as f32 / 2.0,
+                rl.get_screen_height() as f32 / 2.0,
+            );
+            let hit = rl.screen_ground_position(center, &camera, 0.0).unwrap();
+            assert!((hit - Vector3::new(0.0, 0.0, 10.0)).norm() < 1e-2);
+
+            let sky = Vector2::new(center.x, 0.0);
+            let level = Camera3D::perspective(
+                Vector3::y() * 10.0,
+                Vector3::new(0.0, 10.0, 1.0),
+                Vector3::y(),
+                60.0,
+            );
+            assert_eq!(rl.screen_ground_position(sky, &level, 0.0), None);
+        });
+    }
+
+    #[test]
+    #[cfg(all(feature = "integration-tests", feature = "raylib-4-1"))]
+    fn test_render_size_sets_projection_aspect() {
+        use crate::core::camera::Camera3D;
+        use crate::core::drawing::RaylibMode3DExt;
+        use nalgebra::Vector3;
+
+        crate::core::test_harness::with_test_window(|rl, thread| {
+            let scale = rl.get_window_scale_dpi();
+            assert_eq!(
+                rl.get_render_width(),
+                (rl.get_screen_width() as f32 * scale.x).round() as i32
+            );
+            assert_eq!(
+                rl.get_render_height(),
+                (rl.get_screen_height() as f32 * scale.y).round() as i32
+            );
+
+            let camera = Camera3D::perspective(
+                Vector3::new(4.0f32, 3.0, 5.0),
+                Vector3::zeros(),
+                Vector3::y(),
+                60.0,
+            );
+            let proj = camera.get_projection_matrix_default(rl.get_render_aspect());
+            let mut d = rl.begin_drawing(thread);
+            let m3 = d.begin_mode3D(&camera);
+            assert!((m3.get_matrix_projection() - proj).amax() < 1e-4);
+        });
+    }
+}